@@ -0,0 +1,380 @@
+//! A [tokio_util::codec] `Decoder`/`Encoder` for [SessionDescription], for protocols that carry
+//! SDP messages over a stream (like RTSP, MSRP or SAP over TCP). Only available with the `codec`
+//! feature.
+//!
+//! SDP has no framing of its own, so the codec supports two framing modes:
+//! * [Framing::ContentLength]: the length of each SDP message is known beforehand, usually from
+//!   the `Content-Length` header of the carrying protocol, and is given to the codec through
+//!   [SdpCodec::set_content_length] before each message.
+//! * [Framing::BlankLine]: each SDP message is terminated by a blank line, with any of the line
+//!   endings SDP lines may have (`\r\n`, `\r` or `\n`).
+//!
+//! Each message is checked against the [Limits] of the codec (the default ones unless set through
+//! [SdpCodec::with_limits]), and in particular against [Limits::max_size] before it's buffered, so
+//! that a peer can't make the codec buffer an arbitrary amount of bytes.
+
+use crate::{parser_utils::split_lines, Error, Limits, SessionDescription};
+use bytes::{BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+/// How SDP messages are delimited in the stream, see the [module docs](self).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Framing {
+    ContentLength,
+    BlankLine,
+}
+
+/// The SDP codec, see the [module docs](self).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SdpCodec {
+    framing: Framing,
+    content_length: Option<usize>,
+    limits: Limits,
+    //how much of the buffer has been scanned for a blank line, in blank line mode
+    scanned: usize,
+}
+
+impl SdpCodec {
+    /// A codec whose first message has the given length. The length of each of the following
+    /// messages needs to be set through [SdpCodec::set_content_length].
+    pub fn content_length(content_length: usize) -> Self {
+        Self {
+            framing: Framing::ContentLength,
+            content_length: Some(content_length),
+            limits: Limits::default(),
+            scanned: 0,
+        }
+    }
+
+    pub fn blank_line() -> Self {
+        Self {
+            framing: Framing::BlankLine,
+            content_length: None,
+            limits: Limits::default(),
+            scanned: 0,
+        }
+    }
+
+    pub fn with_limits(self, limits: Limits) -> Self {
+        Self { limits, ..self }
+    }
+
+    pub fn framing(&self) -> Framing {
+        self.framing
+    }
+
+    /// Sets the length of the next message, in [Framing::ContentLength] mode. Until it's set,
+    /// nothing is decoded.
+    pub fn set_content_length(&mut self, content_length: usize) {
+        self.content_length = Some(content_length);
+    }
+}
+
+impl SdpCodec {
+    //the end of the first blank line (after the first line) of the buffer, if any, picking up the
+    //scan where the previous call left it. A `\r` at the end of the buffer might be followed by a
+    //`\n`, so the line it ends is scanned again once more bytes arrive.
+    fn blank_line_end(&mut self, src: &[u8]) -> Option<usize> {
+        let mut start = self.scanned;
+        for (line, ending) in split_lines(&src[start..]) {
+            let end = start + line.len() + ending.len();
+            if ending.is_empty() || (ending == b"\r" && end == src.len()) {
+                break;
+            }
+            if line.is_empty() && start > 0 {
+                return Some(end);
+            }
+            start = end;
+        }
+        self.scanned = start;
+
+        None
+    }
+
+    //a frame is a whole message, even if its last line has no line ending, which the tokenizers
+    //need
+    fn parse(&self, mut frame: BytesMut) -> Result<SessionDescription, Error> {
+        if !frame.ends_with(b"\n") && !frame.ends_with(b"\r") {
+            frame.extend_from_slice(b"\r\n");
+        }
+
+        SessionDescription::from_bytes_with_limits(&frame, &self.limits)
+    }
+}
+
+/// The error of the [SdpCodec]: either an I/O error of the underlying stream or an error while
+/// parsing an SDP message.
+#[derive(Debug)]
+pub enum CodecError {
+    Io(std::io::Error),
+    Sdp(Error),
+}
+
+impl Decoder for SdpCodec {
+    type Item = SessionDescription;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.framing {
+            Framing::ContentLength => {
+                let content_length = match self.content_length {
+                    Some(content_length) => content_length,
+                    None => return Ok(None),
+                };
+                if let Err(error) = self.limits.check_size(content_length) {
+                    self.content_length = None;
+                    return Err(error.into());
+                }
+                if src.len() < content_length {
+                    src.reserve(content_length - src.len());
+                    return Ok(None);
+                }
+
+                self.content_length = None;
+                Ok(Some(self.parse(src.split_to(content_length))?))
+            }
+            Framing::BlankLine => match self.blank_line_end(src) {
+                Some(end) => {
+                    self.scanned = 0;
+                    Ok(Some(self.parse(src.split_to(end))?))
+                }
+                //the message is already too large if it's not terminated yet
+                None => {
+                    self.limits.check_size(src.len())?;
+                    Ok(None)
+                }
+            },
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(session_description) => Ok(Some(session_description)),
+            None if src.is_empty() => Ok(None),
+            //in blank line mode, the last message may be terminated by the end of the stream
+            None if self.framing == Framing::BlankLine => {
+                self.scanned = 0;
+                Ok(Some(self.parse(src.split_to(src.len()))?))
+            }
+            None => Err(Error::incomplete(
+                self.content_length
+                    .map(|content_length| content_length - src.len()),
+            )
+            .into()),
+        }
+    }
+}
+
+impl Encoder<SessionDescription> for SdpCodec {
+    type Error = CodecError;
+
+    fn encode(
+        &mut self,
+        session_description: SessionDescription,
+        dst: &mut BytesMut,
+    ) -> Result<(), Self::Error> {
+        self.encode(&session_description, dst)
+    }
+}
+
+impl Encoder<&SessionDescription> for SdpCodec {
+    type Error = CodecError;
+
+    fn encode(
+        &mut self,
+        session_description: &SessionDescription,
+        dst: &mut BytesMut,
+    ) -> Result<(), Self::Error> {
+        dst.put_slice(session_description.to_string().as_bytes());
+        if self.framing == Framing::BlankLine {
+            dst.put_slice(b"\r\n");
+        }
+
+        Ok(())
+    }
+}
+
+impl From<std::io::Error> for CodecError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<Error> for CodecError {
+    fn from(error: Error) -> Self {
+        Self::Sdp(error)
+    }
+}
+
+impl std::fmt::Display for CodecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "io error: {}", error),
+            Self::Sdp(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Sdp(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    const SDP: &str = concat!(
+        "v=0\r\n",
+        "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+        "s=-\r\n",
+        "t=0 0\r\n",
+        "m=audio 49170 RTP/AVP 0\r\n",
+    );
+
+    #[test]
+    fn decode1() {
+        let mut codec = SdpCodec::content_length(SDP.len());
+        let mut src = BytesMut::from(&SDP[..20]);
+
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        src.extend_from_slice(&SDP.as_bytes()[20..]);
+        src.extend_from_slice(b"v=0");
+        assert_eq!(
+            codec.decode(&mut src).unwrap(),
+            Some(SessionDescription::try_from(SDP).unwrap())
+        );
+        assert_eq!(&src[..], b"v=0");
+        assert!(codec.decode(&mut src).unwrap().is_none());
+    }
+
+    #[test]
+    fn decode2() {
+        let mut codec = SdpCodec::blank_line();
+        let mut src = BytesMut::from(SDP);
+
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        src.extend_from_slice(b"\r\n");
+        src.extend_from_slice(SDP.as_bytes());
+        assert_eq!(
+            codec.decode(&mut src).unwrap(),
+            Some(SessionDescription::try_from(SDP).unwrap())
+        );
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        assert_eq!(
+            codec.decode_eof(&mut src).unwrap(),
+            Some(SessionDescription::try_from(SDP).unwrap())
+        );
+    }
+
+    #[test]
+    fn decode3() {
+        let mut codec = SdpCodec::content_length(SDP.len());
+        let mut src = BytesMut::from(&SDP[..20]);
+
+        match codec.decode_eof(&mut src) {
+            Err(CodecError::Sdp(error)) => assert_eq!(
+                error.kind(),
+                &crate::ErrorKind::Incomplete {
+                    needed: Some(SDP.len() - 20)
+                }
+            ),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn decode5() {
+        let sdp = SDP.trim_end();
+        let mut codec = SdpCodec::content_length(sdp.len());
+        let mut src = BytesMut::from(sdp);
+        src.extend_from_slice(b"v=0");
+
+        assert_eq!(
+            codec.decode(&mut src).unwrap(),
+            Some(SessionDescription::try_from(SDP).unwrap())
+        );
+        assert_eq!(&src[..], b"v=0");
+    }
+
+    #[test]
+    fn decode6() {
+        let mut codec = SdpCodec::blank_line();
+        let sdp = SDP.replace("\r\n", "\r");
+        let mut src = BytesMut::from(sdp.as_str());
+
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        src.extend_from_slice(b"\r");
+        //the second `\r` might still be followed by a `\n`
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        src.extend_from_slice(b"v");
+        assert_eq!(
+            codec.decode(&mut src).unwrap(),
+            Some(SessionDescription::try_from(SDP).unwrap())
+        );
+        assert_eq!(&src[..], b"v");
+    }
+
+    #[test]
+    fn decode7() {
+        for ending in &["\n\r\n", "\r\n\n", "\n\r"] {
+            let mut codec = SdpCodec::blank_line();
+            let mut src = BytesMut::from(SDP.trim_end());
+            src.extend_from_slice(ending.as_bytes());
+            src.extend_from_slice(SDP.as_bytes());
+
+            assert_eq!(
+                codec.decode(&mut src).unwrap(),
+                Some(SessionDescription::try_from(SDP).unwrap())
+            );
+            assert_eq!(&src[..], SDP.as_bytes());
+        }
+    }
+
+    #[test]
+    fn decode4() {
+        let limits = Limits {
+            max_size: SDP.len() - 1,
+            ..Default::default()
+        };
+        let exceeded = |result: Result<Option<SessionDescription>, CodecError>| match result {
+            Err(CodecError::Sdp(error)) => assert_eq!(
+                error.kind(),
+                &crate::ErrorKind::LimitExceeded {
+                    limit: crate::Limit::Size
+                }
+            ),
+            other => panic!("unexpected result: {:?}", other),
+        };
+
+        let mut codec = SdpCodec::content_length(SDP.len()).with_limits(limits);
+        let mut src = BytesMut::new();
+        exceeded(codec.decode(&mut src));
+        assert_eq!(src.capacity(), 0);
+
+        let mut codec = SdpCodec::blank_line().with_limits(limits);
+        let mut src = BytesMut::from(SDP);
+        exceeded(codec.decode(&mut src));
+
+        let mut codec = SdpCodec::blank_line().with_limits(Limits::unlimited());
+        let mut src = BytesMut::from(format!("{}\r\n", SDP).as_str());
+        assert!(codec.decode(&mut src).unwrap().is_some());
+    }
+
+    #[test]
+    fn encode1() {
+        let session_description = SessionDescription::try_from(SDP).unwrap();
+        let mut codec = SdpCodec::blank_line();
+        let mut dst = BytesMut::new();
+
+        codec.encode(&session_description, &mut dst).unwrap();
+        assert_eq!(&dst[..], format!("{}\r\n", SDP).as_bytes());
+
+        assert_eq!(codec.decode(&mut dst).unwrap(), Some(session_description));
+    }
+}
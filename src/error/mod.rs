@@ -4,57 +4,258 @@ pub use tokenizer_error::TokenizerError;
 
 use std::{error::Error as StdError, fmt};
 
-/// The `Error` enum indicates that something went wrong.
-/// * `ParseError` signifies that tokenizing the input went through, but converting a token to an
-/// actual type failed.
-/// * `TokenizeError` signified that tokenizing the input failed.
-/// * Incomplete is not used, it is meant for a later release where support of streaming input will
-/// be added (for tokio codecs).
+/// The `Error` struct indicates that something went wrong. Its [ErrorKind] tells what went
+/// wrong, while, when parsing a whole SDP message, its [Location] tells where. Errors that
+/// happened because of another error (like failing to parse the port of the media line because
+/// the number is invalid) keep that error as their cause, available through
+/// `std::error::Error::source`.
+#[derive(Debug, Clone)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub location: Option<Location>,
+    cause: Option<Box<Error>>,
+    position: Option<Position>,
+}
+
+/// The kind of an [Error].
+/// * `MissingLine` signifies that a mandatory line (like `v=`, `o=`, `s=` or `t=`) is missing.
+/// * `UnexpectedLine` signifies that the input was not consumed completely.
+/// * `InvalidLine` signifies that tokenizing a line failed.
+/// * `InvalidNumber`, `InvalidAddress`, `InvalidTime`, `InvalidUri` and `InvalidValue` signify
+///   that tokenizing went through, but converting a token to an actual type failed.
+/// * `InvalidAttribute` signifies that the value of a known attribute could not be parsed.
+/// * `LimitExceeded` signifies that the SDP message exceeds one of the [crate::Limits] of the
+///   parse options.
+/// * `Incomplete` signifies that the input is truncated (see
+///   [crate::SessionDescription::parse_incremental]), `needed` being the minimum number of bytes
+///   that are missing, if known.
+///   Note that `Incomplete` used to be a unit variant of [Error] that was never returned: code
+///   matching on it needs to use `ErrorKind::Incomplete { .. }` now (see the changelog).
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ErrorKind {
+    MissingLine,
+    UnexpectedLine,
+    InvalidLine,
+    InvalidNumber,
+    InvalidAddress,
+    InvalidTime,
+    InvalidUri,
+    InvalidAttribute { name: String },
+    InvalidValue,
+    LimitExceeded { limit: crate::Limit },
+    Incomplete { needed: Option<usize> },
+}
+
+/// Where an [Error] happened in the SDP message: the (1-based) line number and column (in
+/// characters) and the text of the offending line.
 #[derive(Debug, Eq, PartialEq, Clone)]
-pub enum Error {
-    ParseError(String),
-    TokenizeError(String),
-    Incomplete,
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+//the position of the error in the input, to be resolved into a `Location` once the whole input
+//is known: either the length of the input left when tokenizing failed or the address of the
+//token that failed to be parsed
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+enum Position {
+    Remaining(usize),
+    Address(usize),
 }
 
 impl Error {
+    pub fn new(kind: ErrorKind, message: String) -> Self {
+        Self {
+            kind,
+            message,
+            location: None,
+            cause: None,
+            position: None,
+        }
+    }
+
     pub fn tokenizer<S, T>(tuple: (S, T)) -> Self
     where
         S: std::fmt::Display,
         T: std::fmt::Display,
     {
-        Self::TokenizeError(format!("failed to tokenize {}: {}", tuple.0, tuple.1))
+        Self::new(
+            ErrorKind::InvalidLine,
+            format!("failed to tokenize {}: {}", tuple.0, tuple.1),
+        )
     }
 
-    pub fn parser<I>(element: &'static str, input: I) -> Self
+    pub fn parser<I>(kind: ErrorKind, element: &'static str, input: I) -> Self
     where
         I: std::fmt::Display,
     {
-        Self::ParseError(format!("failed to parse {}: {}", element, input))
+        Self::new(kind, format!("failed to parse {}: {}", element, input))
     }
 
-    pub fn parser_with_error<I, E>(element: &'static str, input: I, error: E) -> Self
+    pub fn parser_with_error<I, E>(
+        kind: ErrorKind,
+        element: &'static str,
+        input: I,
+        error: E,
+    ) -> Self
     where
         I: std::fmt::Display,
-        E: std::fmt::Display,
+        E: Into<Error>,
     {
-        Self::ParseError(format!(
-            "failed to parse {} ( {} ): {}",
-            element, error, input
-        ))
+        let cause = error.into();
+
+        Self {
+            kind,
+            message: format!(
+                "failed to parse {} ( {} ): {}",
+                element, cause.message, input
+            ),
+            location: None,
+            position: cause.position,
+            cause: Some(Box::new(cause)),
+        }
+    }
+
+    pub fn incomplete(needed: Option<usize>) -> Self {
+        Self::new(
+            ErrorKind::Incomplete { needed },
+            match needed {
+                Some(needed) => format!("incomplete input, needs at least {} more byte(s)", needed),
+                None => "incomplete input".into(),
+            },
+        )
+    }
+
+    pub fn is_incomplete(&self) -> bool {
+        matches!(self.kind, ErrorKind::Incomplete { .. })
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// The (1-based) line number of the error, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.location.as_ref().map(|location| location.line)
+    }
+
+    /// The (1-based) column of the error, if known.
+    pub fn column(&self) -> Option<usize> {
+        self.location.as_ref().map(|location| location.column)
+    }
+
+    /// The text of the line where the error happened, if known.
+    pub fn text(&self) -> Option<&str> {
+        self.location
+            .as_ref()
+            .map(|location| location.text.as_str())
+    }
+
+    pub fn cause(&self) -> Option<&Error> {
+        self.cause.as_deref()
+    }
+
+    /// Marks the given token of the input as the place where the error happened, unless a more
+    /// precise token is already known. Any remaining input length is relative to the token that
+    /// was converted, so it's replaced.
+    pub(crate) fn in_token(mut self, token: &str) -> Self {
+        if !matches!(self.position, Some(Position::Address(_))) {
+            self.position = Some(Position::Address(token.as_ptr() as usize));
+        }
+
+        self
+    }
+
+    /// Resolves the [Location] of the error in the given input, which must be the whole input
+    /// that was tokenized.
+    pub(crate) fn locate(mut self, input: &str) -> Self {
+        if let (None, Some(offset)) = (&self.location, self.offset_in(input)) {
+            self.location = Some(Location::at(input, offset));
+        }
+
+        self
+    }
+
+    /// The byte offset of the error in the given input, which must be the whole input that was
+    /// tokenized, if known.
+    pub(crate) fn offset_in(&self, input: &str) -> Option<usize> {
+        match self.position {
+            Some(Position::Remaining(remaining)) if remaining <= input.len() => {
+                Some(input.len() - remaining)
+            }
+            Some(Position::Address(address)) => address
+                .checked_sub(input.as_ptr() as usize)
+                .filter(|offset| *offset <= input.len()),
+            _ => None,
+        }
+    }
+}
+
+/// Converts a token into its type, marking the given token as the place of the error on failure.
+pub(crate) fn located<T, U>(token: &str, from: T) -> Result<U, Error>
+where
+    U: std::convert::TryFrom<T, Error = Error>,
+{
+    U::try_from(from).map_err(|error| error.in_token(token))
+}
+
+impl Location {
+    /// The location of the given byte offset in the input.
+    pub(crate) fn at(input: &str, offset: usize) -> Self {
+        let offset = (0..=offset)
+            .rev()
+            .find(|offset| input.is_char_boundary(*offset))
+            .unwrap_or_default();
+        //a single pass over the lines up to the one holding the offset
+        let mut line = 1;
+        let mut line_start = 0;
+        let mut line_end = input.len();
+        for (content, ending) in crate::parser_utils::split_lines(input.as_bytes()) {
+            line_end = line_start + content.len();
+            if offset <= line_end {
+                break;
+            }
+            line += 1;
+            line_start = line_end + ending.len();
+            line_end = input.len();
+        }
+        Self {
+            line,
+            column: input[line_start..offset].chars().count() + 1,
+            text: input[line_start..line_end].into(),
+        }
+    }
+}
+
+impl ErrorKind {
+    /// The kind of an error in the value of the attribute with the given name.
+    pub(crate) fn attribute(name: &str) -> Self {
+        Self::InvalidAttribute { name: name.into() }
     }
 }
 
 impl From<TokenizerError> for Error {
     fn from(from: TokenizerError) -> Self {
-        Self::TokenizeError(from.context)
+        Self {
+            kind: match from.missing_line {
+                true => ErrorKind::MissingLine,
+                false => ErrorKind::InvalidLine,
+            },
+            message: from.context,
+            location: None,
+            cause: None,
+            position: from.remaining.map(Position::Remaining),
+        }
     }
 }
 
 impl From<nom::Err<TokenizerError>> for Error {
     fn from(from: nom::Err<TokenizerError>) -> Self {
         match from {
-            nom::Err::Incomplete(_) => Self::Incomplete,
+            nom::Err::Incomplete(nom::Needed::Size(needed)) => Self::incomplete(Some(needed.get())),
+            nom::Err::Incomplete(nom::Needed::Unknown) => Self::incomplete(None),
             nom::Err::Error(e) => e.into(),
             nom::Err::Failure(e) => e.into(),
         }
@@ -63,24 +264,120 @@ impl From<nom::Err<TokenizerError>> for Error {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::TokenizeError(inner) => write!(f, "tokenizer error: {}", inner),
-            Self::ParseError(inner) => write!(f, "could not parse part: {}", inner),
-            Self::Incomplete => write!(f, "sdp error: incomplete input"),
+        match self.kind {
+            ErrorKind::InvalidLine => write!(f, "tokenizer error: {}", self.message)?,
+            ErrorKind::Incomplete { .. } => write!(f, "sdp error: {}", self.message)?,
+            _ => write!(f, "could not parse part: {}", self.message)?,
         }
+        if let Some(location) = &self.location {
+            write!(
+                f,
+                " (line {}, column {}: {})",
+                location.line, location.column, location.text
+            )?;
+        }
+
+        Ok(())
     }
 }
 
-impl StdError for Error {}
+//the position is only an intermediate step towards the location (and, being an address, differs
+//between two copies of the same input), so it's left out
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.message == other.message
+            && self.location == other.location
+            && self.cause == other.cause
+    }
+}
+
+impl Eq for Error {}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.cause
+            .as_deref()
+            .map(|cause| cause as &(dyn StdError + 'static))
+    }
+}
 
 impl From<std::num::ParseIntError> for Error {
     fn from(error: std::num::ParseIntError) -> Self {
-        Self::ParseError(error.to_string())
+        Self::new(ErrorKind::InvalidNumber, error.to_string())
+    }
+}
+
+impl From<std::num::ParseFloatError> for Error {
+    fn from(error: std::num::ParseFloatError) -> Self {
+        Self::new(ErrorKind::InvalidNumber, error.to_string())
     }
 }
 
 impl From<std::net::AddrParseError> for Error {
     fn from(error: std::net::AddrParseError) -> Self {
-        Self::ParseError(error.to_string())
+        Self::new(ErrorKind::InvalidAddress, error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser_with_error1() {
+        let error = Error::parser_with_error(
+            ErrorKind::InvalidNumber,
+            "media port",
+            "9o",
+            "9o".parse::<u16>().unwrap_err(),
+        );
+
+        assert_eq!(error.kind(), &ErrorKind::InvalidNumber);
+        assert_eq!(
+            error.cause().map(Error::kind),
+            Some(&ErrorKind::InvalidNumber)
+        );
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn parser_with_error2() {
+        let (first, second) = (String::from("4917o"), String::from("4917o"));
+        let error = |input: &str| {
+            Error::parser_with_error(
+                ErrorKind::InvalidNumber,
+                "media port",
+                input,
+                input.parse::<u16>().unwrap_err(),
+            )
+            .in_token(input)
+        };
+
+        assert_eq!(error(&first), error(&second));
+    }
+
+    #[test]
+    fn location1() {
+        assert_eq!(
+            Location::at("v=0\r\no=foo bar\r\n", 7),
+            Location {
+                line: 2,
+                column: 3,
+                text: "o=foo bar".into()
+            }
+        );
+    }
+
+    #[test]
+    fn location2() {
+        assert_eq!(
+            Location::at("v=0\ro=foo\r\ns=bar\nt=0 0", 13),
+            Location {
+                line: 3,
+                column: 3,
+                text: "s=bar".into()
+            }
+        );
     }
 }
@@ -4,6 +4,9 @@ use std::{error::Error as StdError, fmt};
 pub struct TokenizerError {
     pub context: String,
     pub nom_kind: Option<nom::error::ErrorKind>,
+    /// The length of the input left when tokenizing failed, used to locate the error.
+    pub remaining: Option<usize>,
+    pub missing_line: bool,
 }
 
 impl TokenizerError {
@@ -15,14 +18,14 @@ impl TokenizerError {
     where
         I: std::fmt::Display,
     {
+        let input = input.to_string();
+        let (context, remaining) = nom_inner(error);
+
         Self {
-            context: format!(
-                "failed to tokenize {} ( {} ): {}",
-                element,
-                nom_inner(error),
-                input
-            ),
+            context: format!("failed to tokenize {} ( {} ): {}", element, context, input),
             nom_kind: None,
+            remaining: remaining.or(Some(input.len())),
+            missing_line: false,
         }
     }
 
@@ -33,6 +36,17 @@ impl TokenizerError {
         Self {
             context: format!("failed to tokenize {}: {}", element, input),
             nom_kind: None,
+            remaining: None,
+            missing_line: false,
+        }
+    }
+
+    pub fn missing_line(element: &'static str) -> Self {
+        Self {
+            context: format!("failed to tokenize {}: missing line(s)", element),
+            nom_kind: None,
+            remaining: None,
+            missing_line: true,
         }
     }
 }
@@ -62,26 +76,34 @@ impl StdError for TokenizerError {}
 
 impl<T: std::fmt::Display> nom::error::ParseError<T> for TokenizerError {
     fn from_error_kind(input: T, kind: nom::error::ErrorKind) -> Self {
+        let input = input.to_string();
+
         Self {
             context: format!(
                 "could not tokenize or was expecting something else before: {}",
                 input
             ),
             nom_kind: Some(kind),
+            remaining: Some(input.len()),
+            missing_line: false,
         }
     }
     fn append(input: T, kind: nom::error::ErrorKind, _: Self) -> Self {
+        let input = input.to_string();
+
         Self {
             context: format!("could not tokenize: {}", input),
             nom_kind: Some(kind),
+            remaining: Some(input.len()),
+            missing_line: false,
         }
     }
 }
 
-fn nom_inner(e: nom::Err<TokenizerError>) -> String {
+fn nom_inner(e: nom::Err<TokenizerError>) -> (String, Option<usize>) {
     match e {
-        nom::Err::Incomplete(_) => "parsing requires more data".into(),
-        nom::Err::Failure(c) => c.context,
-        nom::Err::Error(c) => c.context,
+        nom::Err::Incomplete(_) => ("parsing requires more data".into(), None),
+        nom::Err::Failure(c) => (c.context, c.remaining),
+        nom::Err::Error(c) => (c.context, c.remaining),
     }
 }
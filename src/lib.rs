@@ -14,10 +14,9 @@
 //!(high level type, line type or sub-line type) can be parsed or be displayed as it would,
 //!so you can work with part of an SDP message, if that's useful for you.
 //!
-//!If you need parsing raw bytes (`&[u8]`) ping us. It is possible but we avoided doing that in the
-//!first place because a) it requires tons of traits/generics which will increase complexity and
-//!compile times b) SDP specification highly recommends that the input is UTF-8 c) performance of
-//!converting the bytes to UTF-8 should be negligible.
+//!Raw bytes (`&[u8]`, like the body of a SIP message) can be parsed using
+//![SessionDescription::from_bytes], which decodes the free text fields (`s=`, `i=`) using the
+//!charset declared by the `a=charset` attribute, when these are not UTF-8.
 //!
 //!## Features
 //!* This thing is _fast_, uses nom for basic message parsing.
@@ -28,6 +27,31 @@
 //!  If you find dealing with nom difficult, you can always open an issue for the desired (missing)
 //!  type. The goal is to add as many typed SDP attributes as possible.
 //!
+//!## Cargo features
+//!* `codec`: a [tokio_util](https://docs.rs/tokio-util) codec for reading and writing SDP messages
+//!  from/to a stream, see the `codec` module.
+//!* `serde`: `Serialize`/`Deserialize` impls for [SessionDescription], [MediaDescription], [Time]
+//!  and all [lines] types, see below. The `string_form` module offers a second representation,
+//!  where each line is serialized as its SDP text.
+//!
+//!## Serde schema
+//!With the `serde` feature, types are (de)serialized following their structure, with a schema
+//!that is kept stable across releases:
+//!* structs are objects with the field names as keys, `Option`s are `null` when missing.
+//!* enums without data (like [lines::attribute::Direction]) are `snake_case` strings, while enums
+//!  with data (like [lines::common::Address]) are objects with the `snake_case` variant name as
+//!  the only key, like `{"ip": "10.47.16.5"}` or `{"other": "foo"}`.
+//!* attributes ([lines::Attribute]) are tagged objects with the `kebab-case` attribute name and
+//!  its typed value, like `{"name": "rtpmap", "value": {"payload_type": 0, ...}}`,
+//!  `{"name": "direction", "value": "sendonly"}` or, for any other attribute,
+//!  `{"name": "other", "value": ["key", "value"]}`.
+//!* [lines::common::TypedTime] keeps its unit: `{"unit": "days", "seconds": 604800}`, the unit
+//!  being one of `seconds`, `minutes`, `hours`, `days` or `none`.
+//!* session numbers ([lines::origin::SessionNumber]) are numbers, unless they don't fit in a `u64`, in
+//!  which case they are strings.
+//!* URIs are strings.
+//!* the times of a [SessionDescription] must not be empty, which is enforced on deserialize.
+//!
 //!## Architecture
 //!Each type in `sdp-rs` has a related tokenizer.
 //!This is not enforced by the type system yet, however very soon this will be the case.
@@ -40,24 +64,39 @@
 //!  of `&str`) are transformed to integers, strings or `sdp-rs` types.
 //!* each `sdp-rs` type implements the `Display` trait and hence has a representation.
 
+//the serde dependency is renamed so that the `serde` feature can enable `vec1/serde` as well
+#[cfg(feature = "serde")]
+extern crate serde_crate as serde;
+
+#[cfg(feature = "codec")]
+pub mod codec;
 mod error;
 pub mod lines;
 mod media_description;
+mod media_description_ref;
+mod parse_options;
+mod preserved_session_description;
 mod session_description;
+mod session_description_ref;
+#[cfg(feature = "serde")]
+pub mod string_form;
 mod time;
 #[doc(hidden)]
 pub mod tokenizers;
 
-pub use error::Error;
 pub(crate) use error::TokenizerError;
+pub use error::{Error, ErrorKind, Location};
 pub use media_description::MediaDescription;
+pub use media_description_ref::MediaDescriptionRef;
+pub use parse_options::{Diagnostic, Limit, Limits, ParseOptions, Severity, Strictness};
+pub use preserved_session_description::PreservedSessionDescription;
 pub use session_description::SessionDescription;
+pub use session_description_ref::SessionDescriptionRef;
 pub use time::Time;
 
 pub(crate) type TResult<'a, T> = Result<(&'a str, T), nom::Err<TokenizerError>>;
 pub(crate) type SResult<'a> = Result<(&'a str, &'a str), nom::Err<TokenizerError>>;
 
-//TODO: add tests
 pub(crate) mod parser_utils {
     use crate::SResult;
 
@@ -79,36 +118,95 @@ pub(crate) mod parser_utils {
         terminated(take_until(" "), tag(" "))(part)
     }
 
+    //the line ends at the first line ending, whatever the line endings of the following lines are
     pub fn until_newline(part: &str) -> SResult {
-        use nom::branch::alt;
-
-        alt((until_crlf, until_cr, until_lf))(part)
-    }
-
-    fn until_crlf(part: &str) -> SResult {
         use nom::{
-            bytes::complete::{tag, take_until},
+            branch::alt,
+            bytes::complete::{tag, take_till},
             sequence::terminated,
         };
 
-        terminated(take_until("\r\n"), tag("\r\n"))(part)
+        terminated(
+            take_till(|c| c == '\r' || c == '\n'),
+            alt((tag("\r\n"), tag("\r"), tag("\n"))),
+        )(part)
     }
 
-    fn until_cr(part: &str) -> SResult {
-        use nom::{
-            bytes::complete::{tag, take_until},
-            sequence::terminated,
-        };
+    //splits the input at the first occurrence of the delimiter, like `str::split_once` which is
+    //newer than the minimum supported rustc version
+    pub fn split_once(part: &str, delimiter: char) -> Option<(&str, &str)> {
+        part.find(delimiter)
+            .map(|index| (&part[..index], &part[index + delimiter.len_utf8()..]))
+    }
 
-        terminated(take_until("\r"), tag("\r"))(part)
+    //the number of line endings (`\r\n`, `\r` or `\n`, like until_newline) in the input
+    pub fn line_endings(input: &str) -> usize {
+        input.matches('\n').count() + input.matches('\r').count() - input.matches("\r\n").count()
     }
 
-    fn until_lf(part: &str) -> SResult {
-        use nom::{
-            bytes::complete::{tag, take_until},
-            sequence::terminated,
-        };
+    //the lines of the input along with their line ending (`\r\n`, `\r` or `\n`, like
+    //until_newline), the last line having no line ending if the input doesn't end with one
+    pub fn split_lines(input: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+        let mut rem = input;
+        std::iter::from_fn(move || {
+            if rem.is_empty() {
+                return None;
+            }
+            let end = rem
+                .iter()
+                .position(|byte| matches!(byte, b'\r' | b'\n'))
+                .unwrap_or(rem.len());
+            let ending = match &rem[end..] {
+                [b'\r', b'\n', ..] => 2,
+                [] => 0,
+                _ => 1,
+            };
+            let line = (&rem[..end], &rem[end..end + ending]);
+            rem = &rem[end + ending..];
+            Some(line)
+        })
+    }
 
-        terminated(take_until("\n"), tag("\n"))(part)
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn until_newline1() {
+            assert_eq!(until_newline("v=0\r\no=foo\r\n"), Ok(("o=foo\r\n", "v=0")));
+            assert_eq!(until_newline("v=0\ro=foo\n"), Ok(("o=foo\n", "v=0")));
+            assert_eq!(until_newline("v=0\no=foo\r"), Ok(("o=foo\r", "v=0")));
+            assert_eq!(until_newline("v=0\n\r\n"), Ok(("\r\n", "v=0")));
+            assert!(until_newline("v=0").is_err());
+        }
+
+        #[test]
+        fn split_lines1() {
+            assert_eq!(
+                split_lines(b"v=0\ro=foo\r\ns=-\n\nt=0 0").collect::<Vec<_>>(),
+                vec![
+                    (&b"v=0"[..], &b"\r"[..]),
+                    (b"o=foo", b"\r\n"),
+                    (b"s=-", b"\n"),
+                    (b"", b"\n"),
+                    (b"t=0 0", b""),
+                ]
+            );
+            assert_eq!(split_lines(b"").next(), None);
+        }
+
+        #[test]
+        fn split_once1() {
+            assert_eq!(split_once("8 urn:foo", ' '), Some(("8", "urn:foo")));
+            assert_eq!(split_once("8/sendonly/", '/'), Some(("8", "sendonly/")));
+            assert_eq!(split_once("8", ' '), None);
+        }
+
+        #[test]
+        fn line_endings1() {
+            assert_eq!(line_endings("v=0\ro=foo\r\ns=-\n\nt=0 0"), 4);
+            assert_eq!(line_endings("v=0\r\r\n"), 2);
+            assert_eq!(line_endings("v=0"), 0);
+        }
     }
 }
@@ -1,33 +1,148 @@
 //! Types related to the time active line (`a=`).
 
-use crate::Error;
-use std::convert::TryFrom;
+use crate::{Error, ErrorKind};
+use chrono::{DateTime, TimeZone, Utc};
+use std::{convert::TryFrom, time::SystemTime};
 
 /// The time active line (`a=`) tokenizer. This is low level stuff and you shouldn't interact
 /// directly with it, unless you know what you are doing.
 pub use crate::tokenizers::time::active::Tokenizer;
 
-/// The time active (`a=`) of SDP. `start` and `stop` are saved as the appear in SDP, you will need
-/// `chrono` (or `std`) to convert to an actual time.
+/// The difference, in seconds, between the NTP epoch (1900-01-01) and the Unix epoch (1970-01-01).
+pub const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+
+/// The time active (`a=`) of SDP. `start` and `stop` are saved as they appear in SDP, that is, as
+/// NTP timestamps in seconds (era 0, counting from 1900-01-01). A `0` value means that the time
+/// is unbounded: a `0` stop time means that the session is active after the start time, while a
+/// `0` start time as well means that the session is permanent.
+///
+/// Conversions to and from `chrono::DateTime<Utc>` and `std::time::SystemTime` are provided,
+/// where an unbounded time is represented by `None`.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Active {
     pub start: u64,
     pub stop: u64,
 }
 
+impl Active {
+    /// Whether the session is permanent, that is both start and stop times are `0`.
+    pub fn is_permanent(&self) -> bool {
+        self.start == 0 && self.stop == 0
+    }
+
+    /// Whether the session has no stop time.
+    pub fn is_unbounded(&self) -> bool {
+        self.stop == 0
+    }
+
+    /// The start time, `None` if it's unbounded.
+    pub fn start_time(&self) -> Option<DateTime<Utc>> {
+        from_ntp(self.start)
+    }
+
+    /// The stop time, `None` if it's unbounded.
+    pub fn stop_time(&self) -> Option<DateTime<Utc>> {
+        from_ntp(self.stop)
+    }
+
+    /// The start time as a `SystemTime`, `None` if it's unbounded.
+    pub fn start_system_time(&self) -> Option<SystemTime> {
+        self.start_time().map(Into::into)
+    }
+
+    /// The stop time as a `SystemTime`, `None` if it's unbounded.
+    pub fn stop_system_time(&self) -> Option<SystemTime> {
+        self.stop_time().map(Into::into)
+    }
+}
+
+impl TryFrom<(Option<DateTime<Utc>>, Option<DateTime<Utc>>)> for Active {
+    type Error = Error;
+
+    fn try_from(
+        (start, stop): (Option<DateTime<Utc>>, Option<DateTime<Utc>>),
+    ) -> Result<Self, Self::Error> {
+        let active = Self {
+            start: start.as_ref().map(to_ntp).transpose()?.unwrap_or(0),
+            stop: stop.as_ref().map(to_ntp).transpose()?.unwrap_or(0),
+        };
+
+        match active.stop != 0 && active.stop < active.start {
+            true => Err(Error::parser_with_error(
+                ErrorKind::InvalidTime,
+                "time stop",
+                active.stop,
+                Error::new(
+                    ErrorKind::InvalidValue,
+                    "stop time before start time".into(),
+                ),
+            )),
+            false => Ok(active),
+        }
+    }
+}
+
+impl TryFrom<(Option<SystemTime>, Option<SystemTime>)> for Active {
+    type Error = Error;
+
+    fn try_from(
+        (start, stop): (Option<SystemTime>, Option<SystemTime>),
+    ) -> Result<Self, Self::Error> {
+        Self::try_from((start.map(DateTime::from), stop.map(DateTime::from)))
+    }
+}
+
+/// Converts an NTP timestamp (in seconds) to a `DateTime`, `0` being unbounded (`None`).
+pub(crate) fn from_ntp(ntp: u64) -> Option<DateTime<Utc>> {
+    match ntp {
+        0 => None,
+        ntp => {
+            let unix = i64::try_from(ntp).ok()? - NTP_UNIX_OFFSET as i64;
+            Utc.timestamp_opt(unix, 0).single()
+        }
+    }
+}
+
+/// Converts a `DateTime` to an NTP timestamp (in seconds). Times at or before the NTP epoch
+/// can't be represented, since `0` means unbounded.
+pub(crate) fn to_ntp(datetime: &DateTime<Utc>) -> Result<u64, Error> {
+    match datetime.timestamp() + NTP_UNIX_OFFSET as i64 {
+        ntp if ntp > 0 => Ok(ntp as u64),
+        _ => Err(Error::parser_with_error(
+            ErrorKind::InvalidTime,
+            "time",
+            datetime,
+            Error::new(ErrorKind::InvalidValue, "time before the NTP epoch".into()),
+        )),
+    }
+}
+
 impl<'a> TryFrom<Tokenizer<'a>> for Active {
     type Error = Error;
 
     fn try_from(tokenizer: Tokenizer<'a>) -> Result<Self, Self::Error> {
         Ok(Self {
-            start: tokenizer
-                .start
-                .parse()
-                .map_err(|e| Self::Error::parser_with_error("time start", tokenizer.start, e))?,
-            stop: tokenizer
-                .stop
-                .parse()
-                .map_err(|e| Self::Error::parser_with_error("time stop", tokenizer.stop, e))?,
+            start: tokenizer.start.parse().map_err(|e| {
+                Self::Error::parser_with_error(
+                    ErrorKind::InvalidTime,
+                    "time start",
+                    tokenizer.start,
+                    e,
+                )
+            })?,
+            stop: tokenizer.stop.parse().map_err(|e| {
+                Self::Error::parser_with_error(
+                    ErrorKind::InvalidTime,
+                    "time stop",
+                    tokenizer.stop,
+                    e,
+                )
+            })?,
         })
     }
 }
@@ -55,6 +170,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn start_time1() {
+        let active = Active {
+            start: 3724394400,
+            stop: 0,
+        };
+
+        assert_eq!(
+            active.start_time(),
+            Some(Utc.with_ymd_and_hms(2018, 1, 8, 10, 0, 0).unwrap())
+        );
+        assert_eq!(active.stop_time(), None);
+        assert!(active.is_unbounded());
+        assert!(!active.is_permanent());
+    }
+
+    #[test]
+    fn from_datetimes1() {
+        assert_eq!(
+            Active::try_from((
+                Some(Utc.with_ymd_and_hms(2018, 1, 8, 10, 0, 0).unwrap()),
+                Some(Utc.with_ymd_and_hms(2018, 1, 8, 11, 0, 0).unwrap())
+            )),
+            Ok(Active {
+                start: 3724394400,
+                stop: 3724398000,
+            })
+        );
+        assert_eq!(
+            Active::try_from((None::<DateTime<Utc>>, None)),
+            Ok(Active { start: 0, stop: 0 })
+        );
+        assert!(Active::try_from((
+            Some(Utc.with_ymd_and_hms(2018, 1, 8, 11, 0, 0).unwrap()),
+            Some(Utc.with_ymd_and_hms(2018, 1, 8, 10, 0, 0).unwrap())
+        ))
+        .is_err());
+        assert!(Active::try_from((
+            Some(Utc.with_ymd_and_hms(1899, 12, 31, 0, 0, 0).unwrap()),
+            None
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn from_system_times1() {
+        let start = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1515405600);
+        let active = Active::try_from((Some(start), None)).unwrap();
+
+        assert_eq!(active.start, 3724394400);
+        assert_eq!(active.start_system_time(), Some(start));
+    }
+
     #[test]
     fn display1() {
         let active = Active {
@@ -0,0 +1,66 @@
+use super::{Attribute, Tokenizer};
+use std::convert::TryFrom;
+
+/// A borrowed attribute line (`a=`), holding the name and the value as they appear in the SDP
+/// message. The value is parsed into an [Attribute] only when asked for, using
+/// [AttributeRef::parse].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
+pub struct AttributeRef<'a> {
+    pub name: &'a str,
+    pub value: Option<&'a str>,
+}
+
+impl<'a> AttributeRef<'a> {
+    pub fn parse(&self) -> Result<Attribute, crate::Error> {
+        Attribute::try_from(Tokenizer::from((self.name, self.value)))
+    }
+}
+
+impl<'a> From<Tokenizer<'a, 'a'>> for AttributeRef<'a> {
+    fn from(tokenizer: Tokenizer<'a, 'a'>) -> Self {
+        Self {
+            name: tokenizer.key,
+            value: tokenizer.value,
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for AttributeRef<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.value {
+            Some(value) => write!(f, "a={}:{}", self.name, value),
+            None => write!(f, "a={}", self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse1() {
+        let attribute = AttributeRef {
+            name: "ptime",
+            value: Some("20"),
+        };
+
+        assert_eq!(attribute.parse(), Ok(Attribute::Ptime(20.0)));
+        assert_eq!(attribute.to_string(), "a=ptime:20");
+    }
+
+    #[test]
+    fn parse2() {
+        let attribute = AttributeRef {
+            name: "ptime",
+            value: Some("foo"),
+        };
+
+        assert!(attribute.parse().is_err());
+    }
+}
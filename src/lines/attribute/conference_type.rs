@@ -2,6 +2,11 @@ use std::convert::TryFrom;
 
 /// The `type` attribute as it appears in the attribute line(s) (`a=`).
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
 pub enum ConferenceType {
     Broadcast,
     Meeting,
@@ -21,6 +26,7 @@ impl<'a> TryFrom<&'a str> for ConferenceType {
             s if s.eq("test") => Ok(Self::Test),
             s if s.eq("H332") => Ok(Self::H332),
             s => Err(crate::Error::parser(
+                crate::ErrorKind::attribute("type"),
                 "type attribute",
                 format!("unknown value `{}`", s),
             )),
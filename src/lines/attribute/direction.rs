@@ -0,0 +1,169 @@
+use super::Attribute;
+use std::convert::TryFrom;
+
+/// The direction attributes (`sendrecv`, `sendonly`, `recvonly` and `inactive`) as they appear in
+/// the attribute line(s) (`a=`). According to [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html)
+/// `sendrecv` is the default, hence the `Default` impl.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
+pub enum Direction {
+    Sendrecv,
+    Sendonly,
+    Recvonly,
+    Inactive,
+}
+
+impl Default for Direction {
+    fn default() -> Self {
+        Self::Sendrecv
+    }
+}
+
+impl Direction {
+    /// The direction that sends and/or receives media as given, i.e. `sendonly` for
+    /// `Direction::new(true, false)` and `inactive` when neither is set.
+    pub fn new(sends: bool, receives: bool) -> Self {
+        match (sends, receives) {
+            (true, true) => Self::Sendrecv,
+            (true, false) => Self::Sendonly,
+            (false, true) => Self::Recvonly,
+            (false, false) => Self::Inactive,
+        }
+    }
+
+    /// Whether media is sent in this direction, which is the case for `sendrecv` and `sendonly`.
+    pub fn sends(&self) -> bool {
+        matches!(self, Self::Sendrecv | Self::Sendonly)
+    }
+
+    /// Whether media is received in this direction, which is the case for `sendrecv` and
+    /// `recvonly`.
+    pub fn receives(&self) -> bool {
+        matches!(self, Self::Sendrecv | Self::Recvonly)
+    }
+
+    /// The direction as seen from the other side, i.e. `sendonly` becomes `recvonly` and vice
+    /// versa.
+    pub fn reverse(&self) -> Self {
+        Self::new(self.receives(), self.sends())
+    }
+
+    /// The direction that is allowed by both `self` and `other`, when both are seen from the same
+    /// side. Useful for creating an answer: `local.intersect(offer.reverse())`.
+    pub fn intersect(&self, other: Self) -> Self {
+        Self::new(
+            self.sends() && other.sends(),
+            self.receives() && other.receives(),
+        )
+    }
+
+    pub(crate) fn find_in(attributes: &[Attribute]) -> Option<Self> {
+        attributes.iter().find_map(|attribute| match attribute {
+            Attribute::Direction(direction) => Some(*direction),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn find_in_refs<'a>(
+        mut attributes: impl Iterator<Item = super::AttributeRef<'a>>,
+    ) -> Option<Self> {
+        attributes.find_map(|attribute| match attribute.value {
+            None => Self::try_from(attribute.name).ok(),
+            Some(_) => None,
+        })
+    }
+
+    pub(crate) fn replace_in(self, attributes: &mut Vec<Attribute>) {
+        match attributes
+            .iter()
+            .position(|attribute| matches!(attribute, Attribute::Direction(_)))
+        {
+            Some(position) => {
+                attributes[position] = Attribute::Direction(self);
+                let mut index = 0;
+                attributes.retain(|attribute| {
+                    index += 1;
+                    index - 1 == position || !matches!(attribute, Attribute::Direction(_))
+                });
+            }
+            None => attributes.push(Attribute::Direction(self)),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Direction {
+    type Error = crate::Error;
+
+    fn try_from(from: &'a str) -> Result<Self, Self::Error> {
+        match from {
+            s if s.eq("sendrecv") => Ok(Self::Sendrecv),
+            s if s.eq("sendonly") => Ok(Self::Sendonly),
+            s if s.eq("recvonly") => Ok(Self::Recvonly),
+            s if s.eq("inactive") => Ok(Self::Inactive),
+            s => Err(crate::Error::parser(
+                crate::ErrorKind::attribute("direction"),
+                "direction attribute",
+                format!("unknown value `{}`", s),
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sendrecv => write!(f, "sendrecv"),
+            Self::Sendonly => write!(f, "sendonly"),
+            Self::Recvonly => write!(f, "recvonly"),
+            Self::Inactive => write!(f, "inactive"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str1() {
+        assert_eq!(Direction::try_from("inactive"), Ok(Direction::Inactive));
+    }
+
+    #[test]
+    fn from_str2() {
+        assert!(Direction::try_from("Sendonly").is_err());
+    }
+
+    #[test]
+    fn reverse1() {
+        assert_eq!(Direction::Sendonly.reverse(), Direction::Recvonly);
+        assert_eq!(Direction::Recvonly.reverse(), Direction::Sendonly);
+        assert_eq!(Direction::Sendrecv.reverse(), Direction::Sendrecv);
+        assert_eq!(Direction::Inactive.reverse(), Direction::Inactive);
+    }
+
+    #[test]
+    fn intersect1() {
+        assert_eq!(
+            Direction::Sendrecv.intersect(Direction::Sendonly),
+            Direction::Sendonly
+        );
+        assert_eq!(
+            Direction::Sendonly.intersect(Direction::Recvonly),
+            Direction::Inactive
+        );
+        assert_eq!(
+            Direction::Sendrecv.intersect(Direction::Sendonly.reverse()),
+            Direction::Recvonly
+        );
+    }
+
+    #[test]
+    fn display1() {
+        assert_eq!(Direction::Recvonly.to_string(), "recvonly");
+    }
+}
@@ -0,0 +1,157 @@
+/// The encoding name as it appears in the `rtpmap` attribute (`a=rtpmap:`). Names spelled
+/// differently than the variant (for instance `vp8` instead of `VP8`) are kept as `Other`, so that
+/// they are displayed back as they were given. Encoding names are case-insensitive, hence
+/// comparisons (`==`, ordering) ignore the case: `EncodingName::from("vp8") == EncodingName::Vp8`.
+/// It's not a `Copy` type since it supports any other encoding name through the `Other` variant.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
+pub enum EncodingName {
+    Pcmu,
+    Pcma,
+    G722,
+    G729,
+    Opus,
+    TelephoneEvent,
+    H264,
+    H265,
+    Vp8,
+    Vp9,
+    Av1,
+    Rtx,
+    Red,
+    Ulpfec,
+    Flexfec,
+    Cn,
+    L16,
+    Other(String),
+}
+
+impl EncodingName {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Pcmu => "PCMU",
+            Self::Pcma => "PCMA",
+            Self::G722 => "G722",
+            Self::G729 => "G729",
+            Self::Opus => "opus",
+            Self::TelephoneEvent => "telephone-event",
+            Self::H264 => "H264",
+            Self::H265 => "H265",
+            Self::Vp8 => "VP8",
+            Self::Vp9 => "VP9",
+            Self::Av1 => "AV1",
+            Self::Rtx => "rtx",
+            Self::Red => "red",
+            Self::Ulpfec => "ulpfec",
+            Self::Flexfec => "flexfec",
+            Self::Cn => "CN",
+            Self::L16 => "L16",
+            Self::Other(inner) => inner,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for EncodingName {
+    fn from(from: &'a str) -> Self {
+        match from {
+            "PCMU" => Self::Pcmu,
+            "PCMA" => Self::Pcma,
+            "G722" => Self::G722,
+            "G729" => Self::G729,
+            "opus" => Self::Opus,
+            "telephone-event" => Self::TelephoneEvent,
+            "H264" => Self::H264,
+            "H265" => Self::H265,
+            "VP8" => Self::Vp8,
+            "VP9" => Self::Vp9,
+            "AV1" => Self::Av1,
+            "rtx" => Self::Rtx,
+            "red" => Self::Red,
+            "ulpfec" => Self::Ulpfec,
+            "flexfec" => Self::Flexfec,
+            "CN" => Self::Cn,
+            "L16" => Self::L16,
+            s => Self::Other(s.into()),
+        }
+    }
+}
+
+impl PartialEq for EncodingName {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str().eq_ignore_ascii_case(other.as_str())
+    }
+}
+
+impl Eq for EncodingName {}
+
+impl Ord for EncodingName {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let lowercase = |name: &Self| {
+            name.as_str()
+                .bytes()
+                .map(|b| b.to_ascii_lowercase())
+                .collect::<Vec<u8>>()
+        };
+        lowercase(self).cmp(&lowercase(other))
+    }
+}
+
+impl PartialOrd for EncodingName {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for EncodingName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str1() {
+        assert_eq!(EncodingName::from("PCMU"), EncodingName::Pcmu);
+    }
+
+    #[test]
+    fn from_str2() {
+        assert_eq!(EncodingName::from("OPUS"), EncodingName::Opus);
+    }
+
+    #[test]
+    fn from_str3() {
+        assert_eq!(
+            EncodingName::from("h263-1998"),
+            EncodingName::Other("h263-1998".into())
+        );
+    }
+
+    #[test]
+    fn display1() {
+        assert_eq!(EncodingName::TelephoneEvent.to_string(), "telephone-event");
+    }
+
+    #[test]
+    fn from_str4() {
+        assert_eq!(EncodingName::from("vp8"), EncodingName::Vp8);
+        assert_eq!(
+            EncodingName::Other("GSM".into()),
+            EncodingName::Other("gsm".into())
+        );
+        assert_ne!(EncodingName::from("vp8"), EncodingName::Vp9);
+    }
+
+    #[test]
+    fn display2() {
+        assert_eq!(EncodingName::from("vp8").to_string(), "vp8");
+        assert_eq!(EncodingName::from("VP8").to_string(), "VP8");
+    }
+}
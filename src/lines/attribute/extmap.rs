@@ -0,0 +1,108 @@
+use crate::{
+    lines::{attribute::Direction, common::UriReference},
+    parser_utils::split_once,
+    Error, ErrorKind,
+};
+use std::convert::TryFrom;
+
+/// The `extmap` attribute as it appears in the attribute line(s) (`a=`), defined in
+/// [RFC8285](https://www.rfc-editor.org/rfc/rfc8285.html): the local identifier of the RTP
+/// header extension, an optional direction, the URI of the extension and its (optional)
+/// extension attributes.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
+pub struct Extmap {
+    pub id: u16,
+    pub direction: Option<Direction>,
+    pub uri: UriReference,
+    pub extension_attributes: Option<String>,
+}
+
+impl<'a> TryFrom<&'a str> for Extmap {
+    type Error = Error;
+
+    fn try_from(part: &'a str) -> Result<Self, Self::Error> {
+        let (value, rem) = split_once(part, ' ').ok_or_else(|| {
+            Error::parser_with_error(
+                ErrorKind::InvalidValue,
+                "extmap",
+                part,
+                Error::new(ErrorKind::InvalidValue, "missing uri".into()),
+            )
+        })?;
+        let (id, direction) = match split_once(value, '/') {
+            Some((id, direction)) => (id, Some(Direction::try_from(direction)?)),
+            None => (value, None),
+        };
+        let (uri, extension_attributes) = match split_once(rem, ' ') {
+            Some((uri, extension_attributes)) => (uri, Some(extension_attributes.into())),
+            None => (rem, None),
+        };
+
+        Ok(Self {
+            id: id.parse().map_err(|e| {
+                Error::parser_with_error(ErrorKind::InvalidNumber, "extmap id", id, e)
+            })?,
+            direction,
+            uri: uri.into(),
+            extension_attributes,
+        })
+    }
+}
+
+impl std::fmt::Display for Extmap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)?;
+        if let Some(direction) = self.direction {
+            write!(f, "/{}", direction)?;
+        }
+        write!(f, " {}", self.uri)?;
+        if let Some(extension_attributes) = &self.extension_attributes {
+            write!(f, " {}", extension_attributes)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str1() {
+        let extmap = Extmap::try_from("1 urn:ietf:params:rtp-hdrext:ssrc-audio-level").unwrap();
+
+        assert_eq!(extmap.id, 1);
+        assert_eq!(extmap.direction, None);
+        assert_eq!(extmap.uri.scheme(), Some("urn"));
+        assert_eq!(extmap.extension_attributes, None);
+    }
+
+    #[test]
+    fn from_str2() {
+        let extmap =
+            Extmap::try_from("2/sendrecv urn:ietf:params:rtp-hdrext:ssrc-audio-level vad=on")
+                .unwrap();
+
+        assert_eq!(extmap.direction, Some(Direction::Sendrecv));
+        assert_eq!(extmap.extension_attributes, Some("vad=on".into()));
+    }
+
+    #[test]
+    fn from_str3() {
+        assert!(Extmap::try_from("x urn:ietf:params:rtp-hdrext:toffset").is_err());
+        assert!(Extmap::try_from("1").is_err());
+    }
+
+    #[test]
+    fn display1() {
+        let part = "2/sendrecv urn:ietf:params:rtp-hdrext:ssrc-audio-level vad=on";
+
+        assert_eq!(Extmap::try_from(part).unwrap().to_string(), part);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{tokenizers::attributes::name_optvalue::Tokenizer as ParamsTokenizer, Error};
+use std::convert::TryFrom;
 
 /// The fmtp tokenizer, which is part of the attribute (`a=`) line. This is low
 /// level stuff and you shouldn't interact directly with it, unless you know what you are doing.
@@ -6,6 +7,11 @@ pub use crate::tokenizers::attributes::fmtp::Tokenizer;
 
 /// The `fmtp` attribute as it appears in the attribute line(s) (`a=`).
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Fmtp {
     pub fmt: String,
     pub params: String,
@@ -15,6 +21,10 @@ impl Fmtp {
     pub fn params_list(&self) -> Result<Vec<(&str, Option<&str>)>, Error> {
         use nom::multi::many1;
 
+        if self.params.is_empty() {
+            return Ok(vec![]);
+        }
+
         let (_, tokenizers) = many1(ParamsTokenizer::tokenize)(&self.params)?;
 
         Ok(tokenizers.into_iter().map(|t| (t.name, t.value)).collect())
@@ -30,16 +40,26 @@ impl<'a> From<Tokenizer<'a>> for Fmtp {
     }
 }
 
+impl<'a> TryFrom<&'a str> for Fmtp {
+    type Error = Error;
+
+    fn try_from(part: &'a str) -> Result<Self, Self::Error> {
+        Ok(Self::from(Tokenizer::tokenize(part)?.1))
+    }
+}
+
 impl std::fmt::Display for Fmtp {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {}", self.fmt, self.params)
+        match self.params.is_empty() {
+            true => write!(f, "{}", self.fmt),
+            false => write!(f, "{} {}", self.fmt, self.params),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::convert::TryFrom;
 
     #[test]
     fn from_str1() {
@@ -49,11 +69,11 @@ mod tests {
         };
 
         assert_eq!(
-            Fmtp::try_from(tokenizer),
-            Ok(Fmtp {
+            Fmtp::from(tokenizer),
+            Fmtp {
                 fmt: "96".into(),
                 params: "profile-level-id=42e016;max-mbps=108000;max-fs=3600".into(),
-            })
+            }
         );
     }
 
@@ -119,4 +139,37 @@ mod tests {
             "96 profile-level-id;max-mbps=108000;max-fs"
         );
     }
+
+    #[test]
+    fn from_str2() {
+        assert_eq!(
+            Fmtp::try_from("101"),
+            Ok(Fmtp {
+                fmt: "101".into(),
+                params: "".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn params_list4() {
+        let fmtp = Fmtp {
+            fmt: "101".into(),
+            params: "".into(),
+        };
+
+        assert_eq!(fmtp.params_list(), Ok(vec![]));
+    }
+
+    #[test]
+    fn display2() {
+        assert_eq!(
+            Fmtp {
+                fmt: "101".into(),
+                params: "".into(),
+            }
+            .to_string(),
+            "101"
+        );
+    }
 }
@@ -5,15 +5,25 @@
 //! open an issue. With nom it should be easy to create a typed form of it instead of dealing with
 //! the `Attribute::Other` variant.
 
+mod attribute_ref;
 mod conference_type;
+mod direction;
+mod encoding_name;
+mod extmap;
 mod fmtp;
 mod orientation;
 mod rtpmap;
+mod silence_supp;
 
+pub use attribute_ref::AttributeRef;
 pub use conference_type::ConferenceType;
+pub use direction::Direction;
+pub use encoding_name::EncodingName;
+pub use extmap::Extmap;
 pub use fmtp::Fmtp;
 pub use orientation::Orientation;
 pub use rtpmap::Rtpmap;
+pub use silence_supp::{SidUse, SilenceSupp, SuppPref};
 
 /// The attribute line (`a=`) tokenizer. This is low level stuff and you shouldn't interact directly
 /// with it, unless you know what you are doing.
@@ -22,17 +32,30 @@ use std::convert::TryFrom;
 
 /// foo bar
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        crate = "serde",
+        tag = "name",
+        content = "value",
+        rename_all = "kebab-case"
+    )
+)]
 pub enum Attribute {
     Cat(String),
     Keywds(String),
     Tool(String),
     Ptime(f32),
     Maxptime(f32),
+    Maxprate(f32),
+    Mptime(Vec<Option<f32>>),
+    SilenceSupp(SilenceSupp),
     Rtpmap(Rtpmap),
-    Recvonly,
-    Sendrecv,
-    Sendonly,
-    Inactive,
+    Fmtp(Fmtp),
+    Extmap(Extmap),
+    Control(crate::lines::common::UriReference),
+    Direction(Direction),
     Orient(Orientation),
     Type(ConferenceType),
     Charset(String),
@@ -53,92 +76,241 @@ impl<'a> TryFrom<Tokenizer<'a, 'a'>> for Attribute {
             (key, Some(value)) if key.eq("keywds") => Self::Keywds(value.into()),
             (key, Some(value)) if key.eq("tool") => Self::Tool(value.into()),
             (key, value) if key.eq("ptime") => match value {
-                Some(value) => value
-                    .parse()
-                    .map(Self::Ptime)
-                    .map_err(|e| crate::Error::parser_with_error("ptime attribute", value, e))?,
+                Some(value) => value.parse().map(Self::Ptime).map_err(|e| {
+                    crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("ptime"),
+                        "ptime attribute",
+                        value,
+                        e,
+                    )
+                })?,
                 None => {
                     return Err(crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("ptime"),
                         "ptime attribute",
                         "",
-                        "missing value",
+                        crate::Error::new(crate::ErrorKind::InvalidValue, "missing value".into()),
                     ))
                 }
             },
             (key, value) if key.eq("maxptime") => match value {
-                Some(value) => value
-                    .parse()
-                    .map(Self::Maxptime)
-                    .map_err(|e| crate::Error::parser_with_error("maxptime attribute", value, e))?,
+                Some(value) => value.parse().map(Self::Maxptime).map_err(|e| {
+                    crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("maxptime"),
+                        "maxptime attribute",
+                        value,
+                        e,
+                    )
+                })?,
                 None => {
                     return Err(crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("maxptime"),
                         "maxptime attribute",
                         "",
-                        "missing value",
+                        crate::Error::new(crate::ErrorKind::InvalidValue, "missing value".into()),
+                    ))
+                }
+            },
+            (key, value) if key.eq("maxprate") => match value {
+                Some(value) => value.parse().map(Self::Maxprate).map_err(|e| {
+                    crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("maxprate"),
+                        "maxprate attribute",
+                        value,
+                        e,
+                    )
+                })?,
+                None => {
+                    return Err(crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("maxprate"),
+                        "maxprate attribute",
+                        "",
+                        crate::Error::new(crate::ErrorKind::InvalidValue, "missing value".into()),
+                    ))
+                }
+            },
+            (key, value) if key.eq("mptime") => match value {
+                Some(value) => Self::Mptime(
+                    value
+                        .split(' ')
+                        .map(|ptime| match ptime {
+                            "-" => Ok(None),
+                            ptime => ptime.parse().map(Some).map_err(|e| {
+                                crate::Error::parser_with_error(
+                                    crate::ErrorKind::attribute("mptime"),
+                                    "mptime attribute",
+                                    ptime,
+                                    e,
+                                )
+                                .in_token(ptime)
+                            }),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                ),
+                None => {
+                    return Err(crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("mptime"),
+                        "mptime attribute",
+                        "",
+                        crate::Error::new(crate::ErrorKind::InvalidValue, "missing value".into()),
+                    ))
+                }
+            },
+            (key, value) if key.eq("silenceSupp") => match value {
+                Some(value) => SilenceSupp::try_from(value)
+                    .map(Self::SilenceSupp)
+                    .map_err(|e| {
+                        crate::Error::parser_with_error(
+                            crate::ErrorKind::attribute("silenceSupp"),
+                            "silenceSupp attribute",
+                            value,
+                            e,
+                        )
+                    })?,
+                None => {
+                    return Err(crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("silenceSupp"),
+                        "silenceSupp attribute",
+                        "",
+                        crate::Error::new(crate::ErrorKind::InvalidValue, "missing value".into()),
+                    ))
+                }
+            },
+            (key, value) if key.eq("rtpmap") => match value {
+                Some(value) => Self::Rtpmap(Rtpmap::try_from(value).map_err(|e| {
+                    crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("rtpmap"),
+                        "rtpmap attribute",
+                        value,
+                        e,
+                    )
+                })?),
+                None => {
+                    return Err(crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("rtpmap"),
+                        "rtpmap attribute",
+                        "",
+                        crate::Error::new(crate::ErrorKind::InvalidValue, "missing value".into()),
+                    ))
+                }
+            },
+            (key, value) if key.eq("fmtp") => match value {
+                Some(value) => Self::Fmtp(Fmtp::try_from(value).map_err(|e| {
+                    crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("fmtp"),
+                        "fmtp attribute",
+                        value,
+                        e,
+                    )
+                })?),
+                None => {
+                    return Err(crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("fmtp"),
+                        "fmtp attribute",
+                        "",
+                        crate::Error::new(crate::ErrorKind::InvalidValue, "missing value".into()),
                     ))
                 }
             },
-            (key, value) if key.eq("rtpmap") => {
-                match value {
-                    Some(value) => Self::Rtpmap(Rtpmap::try_from(value).map_err(|e| {
-                        crate::Error::parser_with_error("rtpmap attribute", value, e)
-                    })?),
-                    None => {
-                        return Err(crate::Error::parser_with_error(
-                            "rtpmap attribute",
-                            "",
-                            "missing value",
-                        ))
-                    }
+            (key, value) if key.eq("extmap") => match value {
+                Some(value) => Self::Extmap(Extmap::try_from(value).map_err(|e| {
+                    crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("extmap"),
+                        "extmap attribute",
+                        value,
+                        e,
+                    )
+                })?),
+                None => {
+                    return Err(crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("extmap"),
+                        "extmap attribute",
+                        "",
+                        crate::Error::new(crate::ErrorKind::InvalidValue, "missing value".into()),
+                    ))
                 }
-            }
-            (key, None) if key.eq("recvonly") => Self::Recvonly,
-            (key, None) if key.eq("sendrecv") => Self::Sendrecv,
-            (key, None) if key.eq("sendonly") => Self::Sendonly,
-            (key, None) if key.eq("inacive") => Self::Inactive,
+            },
+            (key, Some(value)) if key.eq("control") => Self::Control(value.into()),
+            (key, None) if key.eq("recvonly") => Self::Direction(Direction::Recvonly),
+            (key, None) if key.eq("sendrecv") => Self::Direction(Direction::Sendrecv),
+            (key, None) if key.eq("sendonly") => Self::Direction(Direction::Sendonly),
+            (key, None) if key.eq("inactive") => Self::Direction(Direction::Inactive),
             (key, value) if key.eq("orient") => match value {
                 Some(value) => Orientation::try_from(value)
                     .map(Self::Orient)
-                    .map_err(|e| crate::Error::parser_with_error("orient attribute", value, e))?,
+                    .map_err(|e| {
+                        crate::Error::parser_with_error(
+                            crate::ErrorKind::attribute("orient"),
+                            "orient attribute",
+                            value,
+                            e,
+                        )
+                    })?,
                 None => {
                     return Err(crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("orient"),
                         "orient attribute",
                         "",
-                        "missing value",
+                        crate::Error::new(crate::ErrorKind::InvalidValue, "missing value".into()),
                     ))
                 }
             },
             (key, value) if key.eq("type") => match value {
                 Some(value) => ConferenceType::try_from(value)
                     .map(Self::Type)
-                    .map_err(|e| crate::Error::parser_with_error("type attribute", value, e))?,
-                None => return Err(crate::Error::parser_with_error("type", "", "missing value")),
+                    .map_err(|e| {
+                        crate::Error::parser_with_error(
+                            crate::ErrorKind::attribute("type"),
+                            "type attribute",
+                            value,
+                            e,
+                        )
+                    })?,
+                None => {
+                    return Err(crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("type"),
+                        "type attribute",
+                        "",
+                        crate::Error::new(crate::ErrorKind::InvalidValue, "missing value".into()),
+                    ))
+                }
             },
             (key, Some(value)) if key.eq("charset") => Self::Charset(value.into()),
             (key, Some(value)) if key.eq("sdplang") => Self::Sdplang(value.into()),
             (key, Some(value)) if key.eq("lang") => Self::Lang(value.into()),
             (key, value) if key.eq("framerate") => match value {
                 Some(value) => value.parse().map(Self::Framerate).map_err(|e| {
-                    crate::Error::parser_with_error("framerate attribute", value, e)
+                    crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("framerate"),
+                        "framerate attribute",
+                        value,
+                        e,
+                    )
                 })?,
                 None => {
                     return Err(crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("framerate"),
                         "framerate attribute",
                         "",
-                        "missing value",
+                        crate::Error::new(crate::ErrorKind::InvalidValue, "missing value".into()),
                     ))
                 }
             },
             (key, value) if key.eq("quality") => match value {
-                Some(value) => value
-                    .parse()
-                    .map(Self::Quality)
-                    .map_err(|e| crate::Error::parser_with_error("quality attribute", value, e))?,
+                Some(value) => value.parse().map(Self::Quality).map_err(|e| {
+                    crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("quality"),
+                        "quality attribute",
+                        value,
+                        e,
+                    )
+                })?,
                 None => {
                     return Err(crate::Error::parser_with_error(
+                        crate::ErrorKind::attribute("quality"),
                         "quality attribute",
                         "",
-                        "missing value",
+                        crate::Error::new(crate::ErrorKind::InvalidValue, "missing value".into()),
                     ))
                 }
             },
@@ -155,11 +327,25 @@ impl std::fmt::Display for Attribute {
             Self::Tool(tool) => write!(f, "a=tool:{}", tool),
             Self::Ptime(ptime) => write!(f, "a=ptime:{}", ptime),
             Self::Maxptime(maxptime) => write!(f, "a=maxptime:{}", maxptime),
+            Self::Maxprate(maxprate) => write!(f, "a=maxprate:{}", maxprate),
+            Self::Mptime(mptime) => write!(
+                f,
+                "a=mptime:{}",
+                mptime
+                    .iter()
+                    .map(|ptime| match ptime {
+                        Some(ptime) => ptime.to_string(),
+                        None => "-".into(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            Self::SilenceSupp(silence_supp) => write!(f, "a=silenceSupp:{}", silence_supp),
             Self::Rtpmap(rtpmap) => write!(f, "a=rtpmap:{}", rtpmap),
-            Self::Recvonly => write!(f, "a=recvonly"),
-            Self::Sendrecv => write!(f, "a=sendrecv"),
-            Self::Sendonly => write!(f, "a=sendonly"),
-            Self::Inactive => write!(f, "a=inactive"),
+            Self::Fmtp(fmtp) => write!(f, "a=fmtp:{}", fmtp),
+            Self::Extmap(extmap) => write!(f, "a=extmap:{}", extmap),
+            Self::Control(control) => write!(f, "a=control:{}", control),
+            Self::Direction(direction) => write!(f, "a={}", direction),
             Self::Orient(orientation) => write!(f, "a=orient:{}", orientation),
             Self::Type(conference_type) => write!(f, "a=type:{}", conference_type),
             Self::Charset(charset) => write!(f, "a=charset:{}", charset),
@@ -211,6 +397,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_tokenizer5() {
+        let tokenizer: Tokenizer<'a'> = ("mptime", Some("20 30 -")).into();
+
+        assert_eq!(
+            Attribute::try_from(tokenizer).unwrap(),
+            Attribute::Mptime(vec![Some(20.0), Some(30.0), None])
+        );
+    }
+
+    #[test]
+    fn from_tokenizer6() {
+        let tokenizer: Tokenizer<'a'> = ("maxprate", Some("50.0")).into();
+
+        assert_eq!(
+            Attribute::try_from(tokenizer).unwrap(),
+            Attribute::Maxprate(50.0)
+        );
+    }
+
+    #[test]
+    fn from_tokenizer7() {
+        let tokenizer: Tokenizer<'a'> = ("silenceSupp", Some("off - - - -")).into();
+
+        assert_eq!(
+            Attribute::try_from(tokenizer).unwrap(),
+            Attribute::SilenceSupp(SilenceSupp {
+                enabled: Some(false),
+                timer: None,
+                pref: None,
+                sid_use: None,
+                fxnslevel: None,
+            })
+        );
+    }
+
+    #[test]
+    fn from_tokenizer8() {
+        let tokenizer: Tokenizer<'a'> = ("inactive", None).into();
+
+        assert_eq!(
+            Attribute::try_from(tokenizer).unwrap(),
+            Attribute::Direction(Direction::Inactive)
+        );
+    }
+
     #[test]
     fn display1() {
         assert_eq!(
@@ -234,7 +466,10 @@ mod tests {
 
     #[test]
     fn display4() {
-        assert_eq!(Attribute::Recvonly.to_string(), "a=recvonly");
+        assert_eq!(
+            Attribute::Direction(Direction::Recvonly).to_string(),
+            "a=recvonly"
+        );
     }
 
     #[test]
@@ -249,4 +484,72 @@ mod tests {
     fn display6() {
         assert_eq!(Attribute::Other("foo".into(), None).to_string(), "a=foo");
     }
+
+    #[test]
+    fn display7() {
+        assert_eq!(
+            Attribute::Mptime(vec![Some(20.0), Some(30.0), None]).to_string(),
+            "a=mptime:20 30 -"
+        );
+    }
+
+    #[test]
+    fn display8() {
+        assert_eq!(Attribute::Maxprate(50.0).to_string(), "a=maxprate:50");
+    }
+
+    #[test]
+    fn from_tokenizer9() {
+        let tokenizer: Tokenizer<'a'> =
+            ("control", Some("rtsp://example.com/movie/trackID=1")).into();
+
+        let attribute = Attribute::try_from(tokenizer).unwrap();
+        match &attribute {
+            Attribute::Control(uri) => assert_eq!(uri.scheme(), Some("rtsp")),
+            _ => panic!("expected control attribute"),
+        }
+        assert_eq!(
+            attribute.to_string(),
+            "a=control:rtsp://example.com/movie/trackID=1"
+        );
+    }
+
+    #[test]
+    fn from_tokenizer10() {
+        let tokenizer: Tokenizer<'a'> = ("fmtp", Some("101")).into();
+
+        let attribute = Attribute::try_from(tokenizer).unwrap();
+        assert_eq!(
+            attribute,
+            Attribute::Fmtp(Fmtp {
+                fmt: "101".into(),
+                params: "".into(),
+            })
+        );
+        assert_eq!(attribute.to_string(), "a=fmtp:101");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde1() {
+        let attributes = vec![
+            Attribute::Direction(Direction::Sendonly),
+            Attribute::Ptime(20.0),
+            Attribute::Other("foo".into(), Some("bar".into())),
+        ];
+
+        let json = serde_json::to_value(&attributes).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"name": "direction", "value": "sendonly"},
+                {"name": "ptime", "value": 20.0},
+                {"name": "other", "value": ["foo", "bar"]}
+            ])
+        );
+        assert_eq!(
+            serde_json::from_value::<Vec<Attribute>>(json).unwrap(),
+            attributes
+        );
+    }
 }
@@ -2,6 +2,11 @@ use std::convert::TryFrom;
 
 /// The `orient` attribute as it appears in the attribute line(s) (`a=`).
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
 pub enum Orientation {
     Portrait,
     Landscape,
@@ -17,6 +22,7 @@ impl<'a> TryFrom<&'a str> for Orientation {
             s if s.eq("landscape") => Ok(Self::Landscape),
             s if s.eq("seascape") => Ok(Self::Seascape),
             s => Err(crate::Error::parser(
+                crate::ErrorKind::attribute("orient"),
                 "orient attribute",
                 format!("unknown value `{}`", s),
             )),
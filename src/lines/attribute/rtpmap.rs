@@ -1,3 +1,4 @@
+use super::EncodingName;
 /// The rtpmap tokenizer, which is part of the attribute (`a=`) line. This is low
 /// level stuff and you shouldn't interact directly with it, unless you know what you are doing.
 pub use crate::tokenizers::attributes::rtpmap::Tokenizer;
@@ -5,13 +6,60 @@ use std::convert::TryFrom;
 
 /// The `rtpmap` attribute as it appears in the attribute line(s) (`a=`).
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Rtpmap {
-    pub payload_type: u32,
-    pub encoding_name: String,
-    pub clock_rate: i32,
+    pub payload_type: u8,
+    pub encoding_name: EncodingName,
+    pub clock_rate: u32,
     pub encoding_params: Option<i32>,
 }
 
+impl Rtpmap {
+    /// Returns the mapping of a static payload type, as defined in the audio and video encodings
+    /// tables of [RFC3551](https://www.rfc-editor.org/rfc/rfc3551.html#section-6). Returns `None`
+    /// for dynamic, reserved or unassigned payload types.
+    pub fn from_static(payload_type: u8) -> Option<Self> {
+        let (encoding_name, clock_rate, encoding_params) = match payload_type {
+            0 => (EncodingName::Pcmu, 8000, None),
+            3 => ("GSM".into(), 8000, None),
+            4 => ("G723".into(), 8000, None),
+            5 => ("DVI4".into(), 8000, None),
+            6 => ("DVI4".into(), 16000, None),
+            7 => ("LPC".into(), 8000, None),
+            8 => (EncodingName::Pcma, 8000, None),
+            9 => (EncodingName::G722, 8000, None),
+            10 => (EncodingName::L16, 44100, Some(2)),
+            11 => (EncodingName::L16, 44100, None),
+            12 => ("QCELP".into(), 8000, None),
+            13 => (EncodingName::Cn, 8000, None),
+            14 => ("MPA".into(), 90000, None),
+            15 => ("G728".into(), 8000, None),
+            16 => ("DVI4".into(), 11025, None),
+            17 => ("DVI4".into(), 22050, None),
+            18 => (EncodingName::G729, 8000, None),
+            25 => ("CelB".into(), 90000, None),
+            26 => ("JPEG".into(), 90000, None),
+            28 => ("nv".into(), 90000, None),
+            31 => ("H261".into(), 90000, None),
+            32 => ("MPV".into(), 90000, None),
+            33 => ("MP2T".into(), 90000, None),
+            34 => ("H263".into(), 90000, None),
+            _ => return None,
+        };
+
+        Some(Self {
+            payload_type,
+            encoding_name,
+            clock_rate,
+            encoding_params,
+        })
+    }
+}
+
 impl<'a> TryFrom<Tokenizer<'a>> for Rtpmap {
     type Error = crate::Error;
 
@@ -67,7 +115,7 @@ mod tests {
             Rtpmap::try_from(tokenizer),
             Ok(Rtpmap {
                 payload_type: 96,
-                encoding_name: "L8".into(),
+                encoding_name: EncodingName::Other("L8".into()),
                 clock_rate: 8000,
                 encoding_params: None,
             })
@@ -87,19 +135,58 @@ mod tests {
             Rtpmap::try_from(tokenizer),
             Ok(Rtpmap {
                 payload_type: 98,
-                encoding_name: "L16".into(),
+                encoding_name: EncodingName::L16,
                 clock_rate: 16000,
                 encoding_params: Some(2),
             })
         );
     }
 
+    #[test]
+    fn from_str3() {
+        let tokenizer = Tokenizer {
+            payload_type: "111",
+            encoding_name: "OPUS",
+            clock_rate: "48000",
+            encoding_params: Some("2"),
+        };
+
+        assert_eq!(
+            Rtpmap::try_from(tokenizer),
+            Ok(Rtpmap {
+                payload_type: 111,
+                encoding_name: EncodingName::Opus,
+                clock_rate: 48000,
+                encoding_params: Some(2),
+            })
+        );
+    }
+
+    #[test]
+    fn from_static1() {
+        assert_eq!(
+            Rtpmap::from_static(8),
+            Some(Rtpmap {
+                payload_type: 8,
+                encoding_name: EncodingName::Pcma,
+                clock_rate: 8000,
+                encoding_params: None,
+            })
+        );
+    }
+
+    #[test]
+    fn from_static2() {
+        assert_eq!(Rtpmap::from_static(2), None);
+        assert_eq!(Rtpmap::from_static(96), None);
+    }
+
     #[test]
     fn display1() {
         assert_eq!(
             Rtpmap {
                 payload_type: 98,
-                encoding_name: "L16".into(),
+                encoding_name: EncodingName::L16,
                 clock_rate: 16000,
                 encoding_params: Some(2),
             }
@@ -107,4 +194,12 @@ mod tests {
             "98 L16/16000 2"
         );
     }
+
+    #[test]
+    fn display2() {
+        let rtpmap = Rtpmap::try_from("96 vp8/90000").unwrap();
+
+        assert_eq!(rtpmap.encoding_name, EncodingName::Vp8);
+        assert_eq!(rtpmap.to_string(), "96 vp8/90000");
+    }
 }
@@ -0,0 +1,259 @@
+/// The silenceSupp tokenizer, which is part of the attribute (`a=`) line. This is low
+/// level stuff and you shouldn't interact directly with it, unless you know what you are doing.
+pub use crate::tokenizers::attributes::silence_supp::Tokenizer;
+use std::convert::TryFrom;
+
+/// The `silenceSupp` attribute as it appears in the attribute line(s) (`a=`), defined in
+/// [RFC3108](https://www.rfc-editor.org/rfc/rfc3108.html). Each part is optional since it can be
+/// omitted using `-`.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
+pub struct SilenceSupp {
+    pub enabled: Option<bool>,
+    pub timer: Option<u16>,
+    pub pref: Option<SuppPref>,
+    pub sid_use: Option<SidUse>,
+    pub fxnslevel: Option<u8>,
+}
+
+/// The silence suppression preference of the `silenceSupp` attribute.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
+pub enum SuppPref {
+    Standard,
+    Custom,
+}
+
+/// The SID (silence insertion descriptor) use of the `silenceSupp` attribute.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
+pub enum SidUse {
+    NoSid,
+    FixedNoise,
+    SampledNoise,
+}
+
+impl<'a> TryFrom<Tokenizer<'a>> for SilenceSupp {
+    type Error = crate::Error;
+
+    fn try_from(tokenizer: Tokenizer<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            enabled: match tokenizer.enable {
+                "-" => None,
+                "on" => Some(true),
+                "off" => Some(false),
+                s => {
+                    return Err(crate::Error::parser(
+                        crate::ErrorKind::InvalidValue,
+                        "silenceSupp enable",
+                        format!("unknown value `{}`", s),
+                    ))
+                }
+            },
+            timer: optional(tokenizer.timer)
+                .map(|timer| {
+                    timer.parse().map_err(|e| {
+                        crate::Error::parser_with_error(
+                            crate::ErrorKind::InvalidNumber,
+                            "silenceSupp timer",
+                            timer,
+                            e,
+                        )
+                    })
+                })
+                .transpose()?,
+            pref: optional(tokenizer.pref)
+                .map(SuppPref::try_from)
+                .transpose()?,
+            sid_use: optional(tokenizer.sid_use)
+                .map(SidUse::try_from)
+                .transpose()?,
+            fxnslevel: optional(tokenizer.fxnslevel)
+                .map(|level| {
+                    level.parse().map_err(|e| {
+                        crate::Error::parser_with_error(
+                            crate::ErrorKind::InvalidNumber,
+                            "silenceSupp fxnslevel",
+                            level,
+                            e,
+                        )
+                    })
+                })
+                .transpose()?,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for SilenceSupp {
+    type Error = crate::Error;
+
+    fn try_from(part: &'a str) -> Result<Self, Self::Error> {
+        Self::try_from(Tokenizer::tokenize(part)?.1)
+    }
+}
+
+impl std::fmt::Display for SilenceSupp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.enabled {
+            Some(true) => write!(f, "on")?,
+            Some(false) => write!(f, "off")?,
+            None => write!(f, "-")?,
+        }
+        write!(f, " {}", display_optional(self.timer))?;
+        write!(f, " {}", display_optional(self.pref))?;
+        write!(f, " {}", display_optional(self.sid_use))?;
+        write!(f, " {}", display_optional(self.fxnslevel))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for SuppPref {
+    type Error = crate::Error;
+
+    fn try_from(from: &'a str) -> Result<Self, Self::Error> {
+        match from {
+            s if s.eq("standard") => Ok(Self::Standard),
+            s if s.eq("custom") => Ok(Self::Custom),
+            s => Err(crate::Error::parser(
+                crate::ErrorKind::InvalidValue,
+                "silenceSupp pref",
+                format!("unknown value `{}`", s),
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for SuppPref {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Standard => write!(f, "standard"),
+            Self::Custom => write!(f, "custom"),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for SidUse {
+    type Error = crate::Error;
+
+    fn try_from(from: &'a str) -> Result<Self, Self::Error> {
+        match from {
+            s if s.eq("No SID") => Ok(Self::NoSid),
+            s if s.eq("Fixed Noise") => Ok(Self::FixedNoise),
+            s if s.eq("Sampled Noise") => Ok(Self::SampledNoise),
+            s => Err(crate::Error::parser(
+                crate::ErrorKind::InvalidValue,
+                "silenceSupp sidUse",
+                format!("unknown value `{}`", s),
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for SidUse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoSid => write!(f, "No SID"),
+            Self::FixedNoise => write!(f, "Fixed Noise"),
+            Self::SampledNoise => write!(f, "Sampled Noise"),
+        }
+    }
+}
+
+fn optional(part: &str) -> Option<&str> {
+    match part {
+        "-" => None,
+        part => Some(part),
+    }
+}
+
+fn display_optional<T: std::fmt::Display>(value: Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "-".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_tokenizer1() {
+        let tokenizer: Tokenizer = ("off", "-", "-", "-", "-").into();
+
+        assert_eq!(
+            SilenceSupp::try_from(tokenizer),
+            Ok(SilenceSupp {
+                enabled: Some(false),
+                timer: None,
+                pref: None,
+                sid_use: None,
+                fxnslevel: None,
+            })
+        );
+    }
+
+    #[test]
+    fn from_tokenizer2() {
+        let tokenizer: Tokenizer = ("on", "50", "standard", "Sampled Noise", "30").into();
+
+        assert_eq!(
+            SilenceSupp::try_from(tokenizer),
+            Ok(SilenceSupp {
+                enabled: Some(true),
+                timer: Some(50),
+                pref: Some(SuppPref::Standard),
+                sid_use: Some(SidUse::SampledNoise),
+                fxnslevel: Some(30),
+            })
+        );
+    }
+
+    #[test]
+    fn from_tokenizer3() {
+        let tokenizer: Tokenizer = ("maybe", "-", "-", "-", "-").into();
+
+        assert!(SilenceSupp::try_from(tokenizer).is_err());
+    }
+
+    #[test]
+    fn display1() {
+        assert_eq!(
+            SilenceSupp {
+                enabled: Some(false),
+                timer: None,
+                pref: None,
+                sid_use: None,
+                fxnslevel: None,
+            }
+            .to_string(),
+            "off - - - -"
+        );
+    }
+
+    #[test]
+    fn display2() {
+        assert_eq!(
+            SilenceSupp {
+                enabled: Some(true),
+                timer: Some(50),
+                pref: Some(SuppPref::Custom),
+                sid_use: Some(SidUse::NoSid),
+                fxnslevel: None,
+            }
+            .to_string(),
+            "on 50 custom No SID -"
+        );
+    }
+}
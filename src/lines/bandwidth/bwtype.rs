@@ -1,17 +1,65 @@
-/// The Bwtype as it appears in the bandwidth (`b=`) line. It's not a `Copy` type since it
+/// The Bwtype as it appears in the bandwidth (`b=`) line. Apart from `CT` and `AS` of
+/// [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html), it supports `TIAS` of
+/// [RFC3890](https://www.rfc-editor.org/rfc/rfc3890.html) and the RTCP `RR` and `RS` types of
+/// [RFC3556](https://www.rfc-editor.org/rfc/rfc3556.html). It's not a `Copy` type since it
 /// supports abstract types, not even defined in any RFC.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
 pub enum Bwtype {
     Ct,
     As,
+    Tias,
+    Rr,
+    Rs,
     Other(String),
 }
 
+/// The unit in which the value of a bandwidth line is expressed.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
+pub enum BandwidthUnit {
+    Kbps,
+    Bps,
+}
+
+impl BandwidthUnit {
+    /// The number of bits per second of one unit.
+    pub fn bps(&self) -> u64 {
+        match self {
+            Self::Kbps => 1000,
+            Self::Bps => 1,
+        }
+    }
+}
+
+impl Bwtype {
+    /// The unit of the bandwidth value: kbps for `CT`, `AS`, `RR` and `RS`, bps for `TIAS`.
+    /// Unknown types have no known unit.
+    pub fn unit(&self) -> Option<BandwidthUnit> {
+        match self {
+            Self::Ct | Self::As | Self::Rr | Self::Rs => Some(BandwidthUnit::Kbps),
+            Self::Tias => Some(BandwidthUnit::Bps),
+            Self::Other(_) => None,
+        }
+    }
+}
+
 impl<'a> From<&'a str> for Bwtype {
     fn from(s: &str) -> Self {
         match s {
             s if s.eq("CT") => Self::Ct,
             s if s.eq("AS") => Self::As,
+            s if s.eq("TIAS") => Self::Tias,
+            s if s.eq("RR") => Self::Rr,
+            s if s.eq("RS") => Self::Rs,
             _ => Self::Other(s.into()),
         }
     }
@@ -22,6 +70,9 @@ impl std::fmt::Display for Bwtype {
         match self {
             Self::Ct => write!(f, "CT",),
             Self::As => write!(f, "AS",),
+            Self::Tias => write!(f, "TIAS",),
+            Self::Rr => write!(f, "RR",),
+            Self::Rs => write!(f, "RS",),
             Self::Other(other) => write!(f, "{}", other),
         }
     }
@@ -52,6 +103,20 @@ mod tests {
         assert_eq!(Bwtype::from(part), Bwtype::Other("As".into()));
     }
 
+    #[test]
+    fn from_str4() {
+        let part = "TIAS";
+
+        assert_eq!(Bwtype::from(part), Bwtype::Tias);
+    }
+
+    #[test]
+    fn unit1() {
+        assert_eq!(Bwtype::Rs.unit(), Some(BandwidthUnit::Kbps));
+        assert_eq!(Bwtype::Tias.unit(), Some(BandwidthUnit::Bps));
+        assert_eq!(Bwtype::Other("X-YZ".into()).unit(), None);
+    }
+
     #[test]
     fn display1() {
         assert_eq!(Bwtype::As.to_string(), "AS");
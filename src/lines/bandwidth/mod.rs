@@ -2,9 +2,9 @@
 
 mod bwtype;
 
-pub use bwtype::Bwtype;
+pub use bwtype::{BandwidthUnit, Bwtype};
 
-use crate::Error;
+use crate::{Error, ErrorKind};
 use std::convert::TryFrom;
 
 /// The bandwidth line (`b=`) tokenizer. This is low level stuff and you shouldn't interact directly
@@ -12,13 +12,57 @@ use std::convert::TryFrom;
 pub use crate::tokenizers::key_value::Tokenizer;
 
 /// A bandwidth line (`b=`) of SDP, which could appear in the main SDP session or in a Media
-/// description.
+/// description. The `bandwidth` value is expressed in the unit of its [Bwtype], see
+/// [Bwtype::unit].
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Bandwidth {
     pub bwtype: Bwtype,
     pub bandwidth: u32,
 }
 
+impl Bandwidth {
+    /// The bandwidth in bits per second, or `None` if the unit of the [Bwtype] is unknown.
+    pub fn bps(&self) -> Option<u64> {
+        self.bwtype
+            .unit()
+            .map(|unit| u64::from(self.bandwidth) * unit.bps())
+    }
+}
+
+/// The bandwidth budget of a media, in bits per second, as computed by
+/// [crate::MediaDescription::effective_bandwidth].
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
+pub struct EffectiveBandwidth {
+    /// The bandwidth of the media itself.
+    pub media: u64,
+    /// The bandwidth of RTCP reports sent by active data senders (`RS`).
+    pub rtcp_senders: u64,
+    /// The bandwidth of RTCP reports sent by other participants (`RR`).
+    pub rtcp_receivers: u64,
+}
+
+impl EffectiveBandwidth {
+    /// The total RTCP bandwidth.
+    pub fn rtcp(&self) -> u64 {
+        self.rtcp_senders + self.rtcp_receivers
+    }
+
+    /// The total bandwidth, media and RTCP.
+    pub fn total(&self) -> u64 {
+        self.media + self.rtcp()
+    }
+}
+
 impl<'a> TryFrom<Tokenizer<'a, 'b'>> for Bandwidth {
     type Error = Error;
 
@@ -26,7 +70,12 @@ impl<'a> TryFrom<Tokenizer<'a, 'b'>> for Bandwidth {
         Ok(Self {
             bwtype: tokenizer.key.into(),
             bandwidth: tokenizer.value.parse().map_err(|e| {
-                Self::Error::parser_with_error("bandwidth value", tokenizer.value, e)
+                Self::Error::parser_with_error(
+                    ErrorKind::InvalidNumber,
+                    "bandwidth value",
+                    tokenizer.value,
+                    e,
+                )
             })?,
         })
     }
@@ -55,6 +104,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_tokenizer2() {
+        let tokenizer: Tokenizer<'b'> = ("TIAS", "64000").into();
+
+        assert_eq!(
+            Bandwidth::try_from(tokenizer),
+            Ok(Bandwidth {
+                bwtype: Bwtype::Tias,
+                bandwidth: 64000,
+            })
+        );
+    }
+
+    #[test]
+    fn bps1() {
+        let bandwidth = Bandwidth {
+            bwtype: Bwtype::As,
+            bandwidth: 80,
+        };
+
+        assert_eq!(bandwidth.bps(), Some(80_000));
+    }
+
     #[test]
     fn display1() {
         let bandwidth = Bandwidth {
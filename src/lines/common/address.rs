@@ -0,0 +1,188 @@
+use crate::{lines::common::Addrtype, Error, ErrorKind};
+use std::{convert::TryFrom, net::IpAddr};
+
+/// The Address as it appears in the origin (`o=`) or connection (`c=`) lines. According to
+/// [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html) it can be an IP address, a fully
+/// qualified domain name (including mDNS `.local` names) or, for non-IP address types (like the
+/// E.164 numbers of `PSTN` or ATM NSAPs), anything else.
+///
+/// An IP address of the other family than the address type (like `::1` with `IP4`) is kept as
+/// is, [Address::validate] tells whether it matches the address type.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
+pub enum Address {
+    Ip(IpAddr),
+    Fqdn(String),
+    Other(String),
+}
+
+impl Address {
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self {
+            Self::Ip(ip) => Some(*ip),
+            _ => None,
+        }
+    }
+
+    /// Checks that an IP address is of the family of the given address type: an IP6 address
+    /// with the `IP4` address type (or the other way around) is invalid. Parsing only checks it in
+    /// strict mode (see [crate::Strictness]).
+    pub fn validate(&self, addrtype: &Addrtype) -> Result<(), Error> {
+        let error = match (self, addrtype) {
+            (Self::Ip(IpAddr::V6(_)), Addrtype::Ip4) => "IP6 address with IP4 address type",
+            (Self::Ip(IpAddr::V4(_)), Addrtype::Ip6) => "IP4 address with IP6 address type",
+            _ => return Ok(()),
+        };
+
+        Err(Error::parser_with_error(
+            ErrorKind::InvalidAddress,
+            "address",
+            self,
+            Error::new(ErrorKind::InvalidValue, error.into()),
+        ))
+    }
+
+    /// Resolves the address to an `IpAddr`. IP addresses are returned as is, FQDNs are passed to
+    /// the given resolver (for instance a closure around `std::net::ToSocketAddrs` or an async
+    /// resolver's cache) while other addresses can't be resolved.
+    pub fn resolve<F>(&self, resolver: F) -> Option<IpAddr>
+    where
+        F: FnOnce(&str) -> Option<IpAddr>,
+    {
+        match self {
+            Self::Ip(ip) => Some(*ip),
+            Self::Fqdn(fqdn) => resolver(fqdn),
+            Self::Other(_) => None,
+        }
+    }
+}
+
+impl<'a> TryFrom<(&'a str, &Addrtype)> for Address {
+    type Error = Error;
+
+    fn try_from((part, addrtype): (&'a str, &Addrtype)) -> Result<Self, Self::Error> {
+        match addrtype {
+            Addrtype::Ip4 | Addrtype::Ip6 => match part.parse::<IpAddr>() {
+                Ok(ip) => Ok(Self::Ip(ip)),
+                Err(_) => fqdn(part),
+            },
+            Addrtype::Other(_) => Ok(Self::Other(part.into())),
+        }
+    }
+}
+
+impl From<IpAddr> for Address {
+    fn from(ip: IpAddr) -> Self {
+        Self::Ip(ip)
+    }
+}
+
+impl std::fmt::Display for Address {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ip(ip) => write!(f, "{}", ip),
+            Self::Fqdn(fqdn) => write!(f, "{}", fqdn),
+            Self::Other(other) => write!(f, "{}", other),
+        }
+    }
+}
+
+//FQDN = 4*(alpha-numeric / "-" / "."), but a numeric top level label is rejected since that's
+//most probably a malformed IP4 address
+fn fqdn(part: &str) -> Result<Address, Error> {
+    let top_level = part.rsplit('.').next().unwrap_or_default();
+
+    if part.len() >= 4
+        && part
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+        && !top_level.chars().all(|c| c.is_ascii_digit())
+    {
+        Ok(Address::Fqdn(part.into()))
+    } else {
+        Err(Error::parser(ErrorKind::InvalidAddress, "address", part))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str1() {
+        assert_eq!(
+            Address::try_from(("198.51.100.1", &Addrtype::Ip4)),
+            Ok(Address::Ip("198.51.100.1".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn from_str2() {
+        assert_eq!(
+            Address::try_from(("host.example.com", &Addrtype::Ip4)),
+            Ok(Address::Fqdn("host.example.com".into()))
+        );
+    }
+
+    #[test]
+    fn from_str3() {
+        assert_eq!(
+            Address::try_from(("2d9b3c1e-6d1b-4d2a-a6b3-0c8d7b2f4a11.local", &Addrtype::Ip6)),
+            Ok(Address::Fqdn(
+                "2d9b3c1e-6d1b-4d2a-a6b3-0c8d7b2f4a11.local".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn from_str4() {
+        assert_eq!(
+            Address::try_from(("+441234567890", &Addrtype::Other("E164".into()))),
+            Ok(Address::Other("+441234567890".into()))
+        );
+    }
+
+    #[test]
+    fn from_str5() {
+        assert!(Address::try_from(("no ip", &Addrtype::Ip4)).is_err());
+        assert!(Address::try_from(("10.47.16", &Addrtype::Ip4)).is_err());
+    }
+
+    #[test]
+    fn from_str6() {
+        let address = Address::try_from(("::1", &Addrtype::Ip4)).unwrap();
+        assert_eq!(address, Address::Ip("::1".parse().unwrap()));
+        assert!(address.validate(&Addrtype::Ip4).is_err());
+        assert!(address.validate(&Addrtype::Ip6).is_ok());
+
+        let address = Address::try_from(("198.51.100.1", &Addrtype::Ip6)).unwrap();
+        assert_eq!(address, Address::Ip("198.51.100.1".parse().unwrap()));
+        assert!(address.validate(&Addrtype::Ip6).is_err());
+    }
+
+    #[test]
+    fn resolve1() {
+        let address = Address::Fqdn("host.example.com".into());
+
+        assert_eq!(
+            address.resolve(|_| Some("198.51.100.1".parse().unwrap())),
+            Some("198.51.100.1".parse().unwrap())
+        );
+        assert_eq!(
+            Address::Other("+441234567890".into()).resolve(|_| None),
+            None
+        );
+    }
+
+    #[test]
+    fn display1() {
+        assert_eq!(
+            Address::Fqdn("host.example.com".into()).to_string(),
+            "host.example.com"
+        );
+    }
+}
@@ -1,6 +1,11 @@
 /// The Addrtype as it appears in the connection or origin lines. It's not a `Copy` type since it
 /// supports abstract types, not even defined in any RFC.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
 pub enum Addrtype {
     Ip4,
     Ip6,
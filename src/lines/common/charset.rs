@@ -0,0 +1,39 @@
+//the character sets that free text fields (`s=`, `i=`) can be decoded from, as declared by the
+//charset attribute (`a=charset`), other than UTF-8
+
+/// Decodes free text bytes using the declared charset, if it's supported (UTF-8, ISO-8859-1 and
+/// their aliases). Otherwise UTF-8 is used, as it's the default of
+/// [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html), falling back to a lossy decoding.
+pub(crate) fn decode(bytes: &[u8], charset: Option<&str>) -> String {
+    match charset.map(str::to_ascii_lowercase).as_deref() {
+        Some(charset)
+            if [
+                "iso-8859-1",
+                "iso_8859-1",
+                "iso8859-1",
+                "latin1",
+                "l1",
+                "ibm819",
+                "cp819",
+            ]
+            .contains(&charset) =>
+        {
+            bytes.iter().map(|byte| char::from(*byte)).collect()
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode1() {
+        assert_eq!(decode("Café".as_bytes(), None), "Café");
+        assert_eq!(decode(b"Caf\xe9", Some("ISO-8859-1")), "Café");
+        assert_eq!(decode(b"Caf\xe9", None), "Caf\u{fffd}");
+        assert_eq!(decode("é".as_bytes(), Some("ISO-8859-1")), "Ã©");
+        assert_eq!(decode("é".as_bytes(), Some("UTF-8")), "é");
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{Error, ErrorKind};
+
+/// A display name, as it may accompany the address of an email (`e=`) or a phone (`p=`) line.
+/// [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html) allows two syntaxes, both kept so that
+/// the line is written back as it was received.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
+pub struct DisplayName {
+    pub name: String,
+    pub syntax: DisplayNameSyntax,
+}
+
+/// The syntax of a [DisplayName]: `Comment` is `alice@example.com (Alice Smith)` while `Angle`
+/// is `Alice Smith <alice@example.com>`.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
+pub enum DisplayNameSyntax {
+    Comment,
+    Angle,
+}
+
+impl DisplayName {
+    pub fn new(name: String, syntax: DisplayNameSyntax) -> Self {
+        Self { name, syntax }
+    }
+
+    /// Writes the given address along with this display name.
+    pub(crate) fn fmt_with(
+        &self,
+        address: &str,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self.syntax {
+            DisplayNameSyntax::Comment => write!(f, "{} ({})", address, self.name),
+            DisplayNameSyntax::Angle => write!(f, "{} <{}>", self.name, address),
+        }
+    }
+}
+
+/// Splits the value of an email or phone line into the address and the optional display name.
+pub(crate) fn split(
+    element: &'static str,
+    value: &str,
+) -> Result<(String, Option<DisplayName>), Error> {
+    let (address, display_name) = match (value.find('('), value.rfind('<')) {
+        (Some(index), _) if value.ends_with(')') => (
+            value[..index].trim_end(),
+            Some(DisplayName::new(
+                value[index + 1..value.len() - 1].into(),
+                DisplayNameSyntax::Comment,
+            )),
+        ),
+        (_, Some(index)) if value.ends_with('>') => (
+            &value[index + 1..value.len() - 1],
+            Some(DisplayName::new(
+                value[..index].trim_end().into(),
+                DisplayNameSyntax::Angle,
+            )),
+        ),
+        _ => (value, None),
+    };
+
+    match (address, &display_name) {
+        ("", _) => Err(Error::parser_with_error(
+            ErrorKind::InvalidValue,
+            element,
+            value,
+            Error::new(ErrorKind::InvalidValue, "missing address".into()),
+        )),
+        (_, Some(display_name)) if display_name.name.is_empty() => Err(Error::parser_with_error(
+            ErrorKind::InvalidValue,
+            element,
+            value,
+            Error::new(ErrorKind::InvalidValue, "empty display name".into()),
+        )),
+        (address, _) => Ok((address.into(), display_name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split1() {
+        assert_eq!(
+            split("email", "alice@example.com (Alice Smith)"),
+            Ok((
+                "alice@example.com".into(),
+                Some(DisplayName::new(
+                    "Alice Smith".into(),
+                    DisplayNameSyntax::Comment
+                ))
+            ))
+        );
+    }
+
+    #[test]
+    fn split2() {
+        assert_eq!(
+            split("email", "Alice Smith <alice@example.com>"),
+            Ok((
+                "alice@example.com".into(),
+                Some(DisplayName::new(
+                    "Alice Smith".into(),
+                    DisplayNameSyntax::Angle
+                ))
+            ))
+        );
+    }
+
+    #[test]
+    fn split3() {
+        assert_eq!(
+            split("email", "alice@example.com"),
+            Ok(("alice@example.com".into(), None))
+        );
+        assert!(split("email", "Alice Smith <>").is_err());
+        assert!(split("email", "alice@example.com ()").is_err());
+    }
+}
@@ -1,9 +1,18 @@
 //! Common types that might appear in more than one line types of an SDP message.
 
+mod address;
 mod addrtype;
+mod charset;
+mod display_name;
 mod nettype;
 mod typed_time;
+mod uri_reference;
 
+pub use address::Address;
 pub use addrtype::Addrtype;
+pub(crate) use charset::decode as decode_text;
+pub(crate) use display_name::split as split_display_name;
+pub use display_name::{DisplayName, DisplayNameSyntax};
 pub use nettype::Nettype;
 pub use typed_time::TypedTime;
+pub use uri_reference::UriReference;
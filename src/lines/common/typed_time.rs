@@ -1,4 +1,4 @@
-use crate::Error;
+use crate::{Error, ErrorKind};
 use chrono::Duration;
 use std::convert::TryFrom;
 
@@ -6,6 +6,11 @@ use std::convert::TryFrom;
 /// offet line (`z=`). Such times can be in seconds, with or without suffix unit (`s`) but can also
 /// be in other explicit units, specifically minutes (`m`), hours (`h`) and days (`d`).
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", into = "TypedTimeRepr", from = "TypedTimeRepr")
+)]
 pub enum TypedTime {
     Seconds(Duration),
     Minutes(Duration),
@@ -14,6 +19,80 @@ pub enum TypedTime {
     None(Duration),
 }
 
+impl TypedTime {
+    /// The compact form of the given duration, as recommended by
+    /// [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html): the largest unit that expresses
+    /// the duration exactly (days, hours or minutes), or plain seconds without a unit suffix
+    /// otherwise (`0` included).
+    pub fn compact(duration: Duration) -> Self {
+        match duration.num_seconds() {
+            0 => Self::None(duration),
+            seconds if seconds % 86400 == 0 => Self::Days(duration),
+            seconds if seconds % 3600 == 0 => Self::Hours(duration),
+            seconds if seconds % 60 == 0 => Self::Minutes(duration),
+            _ => Self::None(duration),
+        }
+    }
+
+    /// The compact form of this typed time, see [TypedTime::compact].
+    pub fn normalize(self) -> Self {
+        Self::compact(self.into())
+    }
+}
+
+//the serde representation of a typed time: its unit along with the (signed) seconds, since
+//`Duration` has no serde representation of its own
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(crate = "serde")]
+struct TypedTimeRepr {
+    unit: TypedTimeUnit,
+    seconds: i64,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(crate = "serde", rename_all = "snake_case")]
+enum TypedTimeUnit {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    None,
+}
+
+#[cfg(feature = "serde")]
+impl From<TypedTime> for TypedTimeRepr {
+    fn from(typed_time: TypedTime) -> Self {
+        let unit = match typed_time {
+            TypedTime::Seconds(_) => TypedTimeUnit::Seconds,
+            TypedTime::Minutes(_) => TypedTimeUnit::Minutes,
+            TypedTime::Hours(_) => TypedTimeUnit::Hours,
+            TypedTime::Days(_) => TypedTimeUnit::Days,
+            TypedTime::None(_) => TypedTimeUnit::None,
+        };
+
+        Self {
+            unit,
+            seconds: Duration::from(typed_time).num_seconds(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<TypedTimeRepr> for TypedTime {
+    fn from(repr: TypedTimeRepr) -> Self {
+        let duration = Duration::seconds(repr.seconds);
+        match repr.unit {
+            TypedTimeUnit::Seconds => Self::Seconds(duration),
+            TypedTimeUnit::Minutes => Self::Minutes(duration),
+            TypedTimeUnit::Hours => Self::Hours(duration),
+            TypedTimeUnit::Days => Self::Days(duration),
+            TypedTimeUnit::None => Self::None(duration),
+        }
+    }
+}
+
 impl From<Duration> for TypedTime {
     fn from(duration: Duration) -> Self {
         Self::None(duration)
@@ -38,30 +117,28 @@ impl<'a> TryFrom<&'a str> for TypedTime {
     fn try_from(part: &'a str) -> Result<Self, Self::Error> {
         let typed_time = match part.chars().last() {
             Some('s') => Self::Seconds(Duration::seconds(
-                part[0..part.len() - 1]
-                    .parse::<i64>()
-                    .map_err(|e| Self::Error::parser_with_error("typed time", part, e))?,
+                part[0..part.len() - 1].parse::<i64>().map_err(|e| {
+                    Self::Error::parser_with_error(ErrorKind::InvalidTime, "typed time", part, e)
+                })?,
             )),
             Some('m') => Self::Minutes(Duration::minutes(
-                part[0..part.len() - 1]
-                    .parse::<i64>()
-                    .map_err(|e| Self::Error::parser_with_error("typed time", part, e))?,
+                part[0..part.len() - 1].parse::<i64>().map_err(|e| {
+                    Self::Error::parser_with_error(ErrorKind::InvalidTime, "typed time", part, e)
+                })?,
             )),
             Some('h') => Self::Hours(Duration::hours(
-                part[0..part.len() - 1]
-                    .parse::<i64>()
-                    .map_err(|e| Self::Error::parser_with_error("typed time", part, e))?,
+                part[0..part.len() - 1].parse::<i64>().map_err(|e| {
+                    Self::Error::parser_with_error(ErrorKind::InvalidTime, "typed time", part, e)
+                })?,
             )),
             Some('d') => Self::Days(Duration::days(
-                part[0..part.len() - 1]
-                    .parse::<i64>()
-                    .map_err(|e| Self::Error::parser_with_error("typed time", part, e))?,
+                part[0..part.len() - 1].parse::<i64>().map_err(|e| {
+                    Self::Error::parser_with_error(ErrorKind::InvalidTime, "typed time", part, e)
+                })?,
             )),
-            _ => {
-                Self::None(Duration::seconds(part.parse::<i64>().map_err(|e| {
-                    Self::Error::parser_with_error("typed time", part, e)
-                })?))
-            }
+            _ => Self::None(Duration::seconds(part.parse::<i64>().map_err(|e| {
+                Self::Error::parser_with_error(ErrorKind::InvalidTime, "typed time", part, e)
+            })?)),
         };
 
         Ok(typed_time)
@@ -148,6 +225,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compact1() {
+        assert_eq!(
+            TypedTime::compact(Duration::seconds(604800)).to_string(),
+            "7d"
+        );
+        assert_eq!(
+            TypedTime::compact(Duration::seconds(90000)).to_string(),
+            "25h"
+        );
+        assert_eq!(
+            TypedTime::compact(Duration::minutes(-90)).to_string(),
+            "-90m"
+        );
+        assert_eq!(TypedTime::compact(Duration::seconds(90)).to_string(), "90");
+        assert_eq!(TypedTime::compact(Duration::zero()).to_string(), "0");
+    }
+
     #[test]
     fn display1() {
         let typed_time = TypedTime::Hours(Duration::hours(3));
@@ -168,4 +263,17 @@ mod tests {
 
         assert_eq!(typed_time.to_string(), "3m");
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde1() {
+        let typed_time = TypedTime::Days(Duration::days(7));
+
+        let json = serde_json::to_value(typed_time).unwrap();
+        assert_eq!(json, serde_json::json!({"unit": "days", "seconds": 604800}));
+        assert_eq!(
+            serde_json::from_value::<TypedTime>(json).unwrap(),
+            typed_time
+        );
+    }
 }
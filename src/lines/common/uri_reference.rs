@@ -0,0 +1,230 @@
+use crate::{parser_utils::split_once, Error, ErrorKind};
+
+/// A URI reference, as defined in [RFC3986](https://www.rfc-editor.org/rfc/rfc3986.html), that
+/// appears in the uri line (`u=`) or in attributes like `a=extmap` and `a=control`. The URI is
+/// split into its components (scheme, authority, path, query and fragment) using the generic
+/// syntax of the RFC, while the original text is kept as is, for exact round-tripping.
+///
+/// Splitting never fails, since any string can be split into these components; whether the
+/// reference is actually valid (valid scheme, allowed characters and percent-encodings) can be
+/// checked using [UriReference::validate], which is what [UriReference::parse_strict] does.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", into = "String", from = "String")
+)]
+pub struct UriReference {
+    original: String,
+    scheme: Option<String>,
+    authority: Option<String>,
+    path: String,
+    query: Option<String>,
+    fragment: Option<String>,
+}
+
+impl UriReference {
+    /// Splits the given text into the URI components, without validating it.
+    pub fn parse(part: &str) -> Self {
+        let (rem, fragment) = match split_once(part, '#') {
+            Some((rem, fragment)) => (rem, Some(fragment.into())),
+            None => (part, None),
+        };
+        let (rem, query) = match split_once(rem, '?') {
+            Some((rem, query)) => (rem, Some(query.into())),
+            None => (rem, None),
+        };
+        let (scheme, rem) = match rem.find([':', '/']) {
+            Some(index) if index > 0 && rem[index..].starts_with(':') => {
+                (Some(rem[..index].into()), &rem[index + 1..])
+            }
+            _ => (None, rem),
+        };
+        let (authority, path) = match rem.strip_prefix("//") {
+            Some(rem) => match rem.find('/') {
+                Some(index) => (Some(rem[..index].into()), &rem[index..]),
+                None => (Some(rem.into()), ""),
+            },
+            None => (None, rem),
+        };
+
+        Self {
+            original: part.into(),
+            scheme,
+            authority,
+            path: path.into(),
+            query,
+            fragment,
+        }
+    }
+
+    /// Splits the given text into the URI components, failing if it's not a valid URI
+    /// reference.
+    pub fn parse_strict(part: &str) -> Result<Self, Error> {
+        let uri = Self::parse(part);
+        uri.validate()?;
+
+        Ok(uri)
+    }
+
+    /// Checks that the URI reference is not empty, that the scheme (if any) is valid and that it
+    /// consists only of characters allowed by RFC3986, with well formed percent-encodings.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.original.is_empty() {
+            return Err(Error::parser_with_error(
+                ErrorKind::InvalidUri,
+                "uri",
+                "",
+                Error::new(ErrorKind::InvalidValue, "empty uri".into()),
+            ));
+        }
+
+        if let Some(scheme) = &self.scheme {
+            let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.');
+            if !valid {
+                return Err(Error::parser_with_error(
+                    ErrorKind::InvalidUri,
+                    "uri",
+                    &self.original,
+                    Error::new(ErrorKind::InvalidValue, "invalid scheme".into()),
+                ));
+            }
+        }
+
+        let bytes = self.original.as_bytes();
+        for (index, byte) in bytes.iter().enumerate() {
+            let valid = match byte {
+                b'%' => {
+                    matches!(bytes.get(index + 1), Some(byte) if byte.is_ascii_hexdigit())
+                        && matches!(bytes.get(index + 2), Some(byte) if byte.is_ascii_hexdigit())
+                }
+                byte => byte.is_ascii_alphanumeric() || b"-._~:/?#[]@!$&'()*+,;=".contains(byte),
+            };
+            if !valid {
+                return Err(Error::parser_with_error(
+                    ErrorKind::InvalidUri,
+                    "uri",
+                    &self.original,
+                    Error::new(ErrorKind::InvalidValue, "invalid character".into()),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Whether the URI reference is an absolute URI, that is it has a scheme.
+    pub fn is_absolute(&self) -> bool {
+        self.scheme.is_some()
+    }
+
+    /// The original text of the URI reference.
+    pub fn as_str(&self) -> &str {
+        &self.original
+    }
+
+    pub fn scheme(&self) -> Option<&str> {
+        self.scheme.as_deref()
+    }
+
+    pub fn authority(&self) -> Option<&str> {
+        self.authority.as_deref()
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+}
+
+impl<'a> From<&'a str> for UriReference {
+    fn from(part: &'a str) -> Self {
+        Self::parse(part)
+    }
+}
+
+impl From<String> for UriReference {
+    fn from(part: String) -> Self {
+        Self::parse(&part)
+    }
+}
+
+impl From<UriReference> for String {
+    fn from(uri: UriReference) -> Self {
+        uri.original
+    }
+}
+
+impl std::fmt::Display for UriReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.original)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse1() {
+        let uri = UriReference::parse("http://www.jdoe.example.com:8080/home.html?lang=en#top");
+
+        assert_eq!(uri.scheme(), Some("http"));
+        assert_eq!(uri.authority(), Some("www.jdoe.example.com:8080"));
+        assert_eq!(uri.path(), "/home.html");
+        assert_eq!(uri.query(), Some("lang=en"));
+        assert_eq!(uri.fragment(), Some("top"));
+        assert!(uri.is_valid());
+    }
+
+    #[test]
+    fn parse2() {
+        let uri = UriReference::parse("urn:ietf:params:rtp-hdrext:ssrc-audio-level");
+
+        assert_eq!(uri.scheme(), Some("urn"));
+        assert_eq!(uri.authority(), None);
+        assert_eq!(uri.path(), "ietf:params:rtp-hdrext:ssrc-audio-level");
+        assert!(uri.is_absolute());
+    }
+
+    #[test]
+    fn parse3() {
+        let uri = UriReference::parse("trackID=1");
+
+        assert_eq!(uri.scheme(), None);
+        assert_eq!(uri.path(), "trackID=1");
+        assert!(!uri.is_absolute());
+        assert!(uri.is_valid());
+    }
+
+    #[test]
+    fn parse_strict1() {
+        assert!(UriReference::parse_strict("http://example.com/a b").is_err());
+        assert!(UriReference::parse_strict("http://example.com/%zz").is_err());
+        assert!(UriReference::parse_strict("1http://example.com").is_err());
+        assert!(UriReference::parse_strict("").is_err());
+        assert!(UriReference::parse_strict("rtsp://example.com/%20").is_ok());
+    }
+
+    #[test]
+    fn display1() {
+        assert_eq!(
+            UriReference::parse("HTTP://Example.com/%7e").to_string(),
+            "HTTP://Example.com/%7e"
+        );
+    }
+}
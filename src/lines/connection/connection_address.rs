@@ -1,63 +1,167 @@
-use crate::Error;
+use crate::{
+    lines::common::{Address, Addrtype},
+    Error, ErrorKind,
+};
 use std::{convert::TryFrom, net::IpAddr};
 
 /// The connection address tokenizer, which is part of the connection (`c=`) line. This is low
 /// level stuff and you shouldn't interact directly with it, unless you know what you are doing.
 pub use crate::tokenizers::connection::connection_address::Tokenizer;
 
-/// The connection address of the connection line (`c=`).
-#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+/// The connection address of the connection line (`c=`). Since the base address is parsed
+/// according to the address type of the connection line, it can only be parsed together with
+/// an [Addrtype].
+///
+/// The meaning of the slash separated suffixes depends on the address family: an IP4 multicast
+/// address is followed by a mandatory TTL and optionally the number of addresses (`224.2.1.1/127/3`),
+/// while an IP6 multicast address can only be followed by the number of addresses
+/// (`FF15::101/3`). Unicast addresses take no suffix at all. For any other base address a single
+/// suffix is the TTL, hence a number of addresses requires a TTL.
+///
+/// It's no longer a `Copy` type, since the base address isn't necessarily an IP address.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct ConnectionAddress {
-    pub base: IpAddr,
-    pub ttl: Option<u32>,
+    pub base: Address,
+    pub ttl: Option<u8>,
     pub numaddr: Option<u32>,
 }
 
-impl<'a> TryFrom<Tokenizer<'a>> for ConnectionAddress {
+impl ConnectionAddress {
+    /// Iterates over the addresses of the multicast block, starting from the base address. For
+    /// a unicast address (or a block without a number of addresses) that's only the base
+    /// address, while non-IP addresses yield nothing.
+    pub fn addresses(&self) -> impl Iterator<Item = IpAddr> {
+        let base = self.base.ip();
+        let count = match base {
+            Some(_) => self.numaddr.unwrap_or(1),
+            None => 0,
+        };
+
+        //stops at the first address that overflows
+        (0..count).scan((), move |_, offset| match base? {
+            IpAddr::V4(ip) => u32::from(ip)
+                .checked_add(offset)
+                .map(|ip| IpAddr::V4(ip.into())),
+            IpAddr::V6(ip) => u128::from(ip)
+                .checked_add(offset.into())
+                .map(|ip| IpAddr::V6(ip.into())),
+        })
+    }
+}
+
+impl<'a> TryFrom<(Tokenizer<'a>, &Addrtype)> for ConnectionAddress {
     type Error = Error;
 
-    fn try_from(tokenizer: Tokenizer<'a>) -> Result<Self, Self::Error> {
-        Ok(Self {
-            base: tokenizer.base.parse().map_err(|e| {
-                Self::Error::parser_with_error("connection address base", tokenizer.base, e)
-            })?,
-            ttl: tokenizer
-                .ttl
-                .map(|ttl| {
-                    ttl.parse().map_err(|e| {
-                        Self::Error::parser_with_error("connection address ttl", ttl, e)
-                    })
+    fn try_from((tokenizer, addrtype): (Tokenizer<'a>, &Addrtype)) -> Result<Self, Self::Error> {
+        let base = Address::try_from((tokenizer.base, addrtype)).map_err(|e| {
+            Self::Error::parser_with_error(
+                ErrorKind::InvalidAddress,
+                "connection address base",
+                tokenizer.base,
+                e,
+            )
+        })?;
+
+        //an IP6 multicast address is only followed by the number of addresses
+        let (ttl, numaddr) = match (&base, tokenizer.ttl, tokenizer.numaddr) {
+            (Address::Ip(IpAddr::V6(ip)), Some(numaddr), None) if ip.is_multicast() => {
+                (None, Some(numaddr))
+            }
+            (_, ttl, numaddr) => (ttl, numaddr),
+        };
+
+        let ttl = ttl
+            .map(|ttl| {
+                ttl.parse().map_err(|e| {
+                    Self::Error::parser_with_error(
+                        ErrorKind::InvalidNumber,
+                        "connection address ttl",
+                        ttl,
+                        e,
+                    )
                 })
-                .transpose()?,
-            numaddr: tokenizer
-                .numaddr
-                .map(|numaddr| {
-                    numaddr.parse().map_err(|e| {
-                        Self::Error::parser_with_error("connection number of addresses", numaddr, e)
-                    })
+            })
+            .transpose()?;
+        let numaddr = numaddr
+            .map(|numaddr| {
+                numaddr.parse().map_err(|e| {
+                    Self::Error::parser_with_error(
+                        ErrorKind::InvalidNumber,
+                        "connection number of addresses",
+                        numaddr,
+                        e,
+                    )
                 })
-                .transpose()?,
-        })
+            })
+            .transpose()?;
+
+        Self::new(base, ttl, numaddr)
+    }
+}
+
+impl ConnectionAddress {
+    /// Creates a connection address, checking that the TTL and the number of addresses are
+    /// allowed for the address family of the base address: an IP4 multicast address requires a
+    /// TTL, an IP6 multicast address can't have one and a unicast address can't have either.
+    /// Non-IP addresses are only checked to have a TTL along with a number of addresses. The
+    /// number of addresses can't be zero for any address.
+    pub fn new(base: Address, ttl: Option<u8>, numaddr: Option<u32>) -> Result<Self, Error> {
+        let error = match (&base, ttl, numaddr) {
+            (_, _, Some(0)) => Some("number of addresses can't be zero"),
+            (Address::Ip(IpAddr::V4(ip)), None, _) if ip.is_multicast() => {
+                Some("IP4 multicast address requires a TTL")
+            }
+            (Address::Ip(IpAddr::V4(ip)), Some(_), _) if ip.is_multicast() => None,
+            (Address::Ip(IpAddr::V6(ip)), Some(_), _) if ip.is_multicast() => {
+                Some("IP6 multicast address can't have a TTL")
+            }
+            (Address::Ip(IpAddr::V6(ip)), None, _) if ip.is_multicast() => None,
+            (Address::Other(_), None, Some(_)) => {
+                Some("number of addresses without a TTL for a non-IP address")
+            }
+            (Address::Other(_), _, _) | (_, None, None) => None,
+            _ => Some("unicast address can't have a TTL or number of addresses"),
+        };
+
+        match error {
+            Some(error) => Err(Error::parser_with_error(
+                ErrorKind::InvalidAddress,
+                "connection address",
+                &base,
+                Error::new(ErrorKind::InvalidValue, error.into()),
+            )),
+            None => Ok(Self { base, ttl, numaddr }),
+        }
     }
 }
 
 impl<'a> From<IpAddr> for ConnectionAddress {
     fn from(base: IpAddr) -> Self {
         Self {
-            base,
+            base: base.into(),
             ttl: None,
             numaddr: None,
         }
     }
 }
 
+//a single suffix only reads back as the number of addresses for an IP6 address, otherwise it's
+//the TTL: a number of addresses without a TTL (which `new` rejects) is left out for any other
+//address, instead of being written as a TTL
 impl std::fmt::Display for ConnectionAddress {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match (self.ttl, self.numaddr) {
-            (Some(ttl), Some(numaddr)) => write!(f, "{}/{}/{}", self.base, ttl, numaddr),
-            (Some(ttl), None) => write!(f, "{}/{}", self.base, ttl),
-            (None, Some(numaddr)) => write!(f, "{}/{}", self.base, numaddr),
-            (None, None) => write!(f, "{}", self.base),
+        match (&self.base, self.ttl, self.numaddr) {
+            (_, Some(ttl), Some(numaddr)) => write!(f, "{}/{}/{}", self.base, ttl, numaddr),
+            (_, Some(ttl), None) => write!(f, "{}/{}", self.base, ttl),
+            (Address::Ip(IpAddr::V6(_)), None, Some(numaddr)) => {
+                write!(f, "{}/{}", self.base, numaddr)
+            }
+            (_, None, _) => write!(f, "{}", self.base),
         }
     }
 }
@@ -68,12 +172,12 @@ mod tests {
 
     #[test]
     fn from_tokenizer1() {
-        let tokenizer: Tokenizer = "233.252.0.1".into();
+        let tokenizer: Tokenizer = "198.51.100.1".into();
 
         assert_eq!(
-            ConnectionAddress::try_from(tokenizer),
+            ConnectionAddress::try_from((tokenizer, &Addrtype::Ip4)),
             Ok(ConnectionAddress {
-                base: "233.252.0.1".parse().unwrap(),
+                base: "198.51.100.1".parse::<IpAddr>().unwrap().into(),
                 ttl: None,
                 numaddr: None
             })
@@ -85,9 +189,9 @@ mod tests {
         let tokenizer: Tokenizer = ("233.252.0.1", Some("127"), None).into();
 
         assert_eq!(
-            ConnectionAddress::try_from(tokenizer),
+            ConnectionAddress::try_from((tokenizer, &Addrtype::Ip4)),
             Ok(ConnectionAddress {
-                base: "233.252.0.1".parse().unwrap(),
+                base: "233.252.0.1".parse::<IpAddr>().unwrap().into(),
                 ttl: Some(127),
                 numaddr: None
             })
@@ -99,23 +203,152 @@ mod tests {
         let tokenizer: Tokenizer = ("233.252.0.1", Some("127"), Some("2")).into();
 
         assert_eq!(
-            ConnectionAddress::try_from(tokenizer),
+            ConnectionAddress::try_from((tokenizer, &Addrtype::Ip4)),
             Ok(ConnectionAddress {
-                base: "233.252.0.1".parse().unwrap(),
+                base: "233.252.0.1".parse::<IpAddr>().unwrap().into(),
                 ttl: Some(127),
                 numaddr: Some(2)
             })
         );
     }
 
+    #[test]
+    fn from_tokenizer4() {
+        let tokenizer: Tokenizer = "host.example.com".into();
+
+        assert_eq!(
+            ConnectionAddress::try_from((tokenizer, &Addrtype::Ip6)),
+            Ok(ConnectionAddress {
+                base: Address::Fqdn("host.example.com".into()),
+                ttl: None,
+                numaddr: None
+            })
+        );
+    }
+
     #[test]
     fn display1() {
         let connection = ConnectionAddress {
-            base: "233.252.0.1".parse().unwrap(),
+            base: "233.252.0.1".parse::<IpAddr>().unwrap().into(),
             ttl: Some(127),
             numaddr: Some(2),
         };
 
         assert_eq!(connection.to_string(), "233.252.0.1/127/2");
     }
+
+    #[test]
+    fn from_tokenizer5() {
+        let tokenizer: Tokenizer = ("ff15::101", Some("3"), None).into();
+
+        assert_eq!(
+            ConnectionAddress::try_from((tokenizer, &Addrtype::Ip6)),
+            Ok(ConnectionAddress {
+                base: "ff15::101".parse::<IpAddr>().unwrap().into(),
+                ttl: None,
+                numaddr: Some(3)
+            })
+        );
+    }
+
+    #[test]
+    fn from_tokenizer6() {
+        let tokenizer: Tokenizer = ("ff15::101", Some("127"), Some("3")).into();
+        assert!(ConnectionAddress::try_from((tokenizer, &Addrtype::Ip6)).is_err());
+
+        let tokenizer: Tokenizer = ("198.51.100.1", Some("127"), None).into();
+        assert!(ConnectionAddress::try_from((tokenizer, &Addrtype::Ip4)).is_err());
+
+        let tokenizer: Tokenizer = ("2001:db8::1", Some("2"), None).into();
+        assert!(ConnectionAddress::try_from((tokenizer, &Addrtype::Ip6)).is_err());
+
+        let tokenizer: Tokenizer = ("233.252.0.1", Some("256"), None).into();
+        assert!(ConnectionAddress::try_from((tokenizer, &Addrtype::Ip4)).is_err());
+
+        let tokenizer: Tokenizer = "233.252.0.1".into();
+        assert!(ConnectionAddress::try_from((tokenizer, &Addrtype::Ip4)).is_err());
+    }
+
+    #[test]
+    fn new1() {
+        let base: Address = "224.2.1.1".parse::<IpAddr>().unwrap().into();
+
+        assert!(ConnectionAddress::new(base.clone(), Some(127), Some(3)).is_ok());
+        assert!(ConnectionAddress::new(base.clone(), None, Some(3)).is_err());
+        assert!(ConnectionAddress::new(base.clone(), Some(127), Some(0)).is_err());
+        assert!(ConnectionAddress::new(base, None, None).is_err());
+
+        let base: Address = "ff15::101".parse::<IpAddr>().unwrap().into();
+        assert!(ConnectionAddress::new(base, None, Some(0)).is_err());
+    }
+
+    #[test]
+    fn addresses1() {
+        let connection = ConnectionAddress {
+            base: "224.2.1.1".parse::<IpAddr>().unwrap().into(),
+            ttl: Some(127),
+            numaddr: Some(3),
+        };
+
+        assert_eq!(
+            connection.addresses().collect::<Vec<_>>(),
+            vec![
+                "224.2.1.1".parse::<IpAddr>().unwrap(),
+                "224.2.1.2".parse().unwrap(),
+                "224.2.1.3".parse().unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn addresses2() {
+        let connection = ConnectionAddress {
+            base: Address::Fqdn("host.example.com".into()),
+            ttl: None,
+            numaddr: None,
+        };
+
+        assert_eq!(connection.addresses().count(), 0);
+        assert_eq!(
+            ConnectionAddress::from("ff15::101".parse::<IpAddr>().unwrap())
+                .addresses()
+                .collect::<Vec<_>>(),
+            vec!["ff15::101".parse::<IpAddr>().unwrap()]
+        );
+    }
+
+    #[test]
+    fn display2() {
+        let connection = ConnectionAddress {
+            base: "ff15::101".parse::<IpAddr>().unwrap().into(),
+            ttl: None,
+            numaddr: Some(3),
+        };
+
+        assert_eq!(connection.to_string(), "ff15::101/3");
+    }
+
+    #[test]
+    fn display3() {
+        let connection = ConnectionAddress {
+            base: "224.2.1.1".parse::<IpAddr>().unwrap().into(),
+            ttl: None,
+            numaddr: Some(3),
+        };
+
+        assert_eq!(connection.to_string(), "224.2.1.1");
+    }
+
+    #[test]
+    fn display4() {
+        let connection = ConnectionAddress {
+            base: Address::Other("example".into()),
+            ttl: None,
+            numaddr: Some(3),
+        };
+
+        assert_eq!(connection.to_string(), "example");
+        assert!(ConnectionAddress::new(connection.base, None, Some(3)).is_err());
+        assert!(ConnectionAddress::new(Address::Other("example".into()), Some(1), Some(3)).is_ok());
+    }
 }
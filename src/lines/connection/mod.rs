@@ -11,12 +11,17 @@ use crate::{
     lines::common::{Addrtype, Nettype},
     Error,
 };
-use std::convert::{TryFrom, TryInto};
+use std::convert::TryFrom;
 
 /// A connection line (`c=`) of SDP. Note that more than one such line could exist in an SDP
 /// message, that's why [crate::MediaDescription] has a `Vec<Connection>` defined. But it can
 /// appear at most once in the main description ([crate::SessionDescription]).
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Connection {
     pub nettype: Nettype,
     pub addrtype: Addrtype,
@@ -27,10 +32,15 @@ impl<'a> TryFrom<Tokenizer<'a>> for Connection {
     type Error = Error;
 
     fn try_from(tokenizer: Tokenizer<'a>) -> Result<Self, Self::Error> {
+        let addrtype = tokenizer.addrtype.into();
+
         Ok(Self {
             nettype: tokenizer.nettype.into(),
-            addrtype: tokenizer.addrtype.into(),
-            connection_address: tokenizer.connection_address.try_into()?,
+            connection_address: ConnectionAddress::try_from((
+                tokenizer.connection_address,
+                &addrtype,
+            ))?,
+            addrtype,
         })
     }
 }
@@ -48,10 +58,12 @@ impl std::fmt::Display for Connection {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lines::common::Address;
+    use std::net::IpAddr;
 
     #[test]
     fn from_tokenizer1() {
-        let tokenizer: Tokenizer = ("IN", "IP4", ("233.252.0.1")).into();
+        let tokenizer: Tokenizer = ("IN", "IP4", ("198.51.100.1")).into();
 
         assert_eq!(
             Connection::try_from(tokenizer),
@@ -59,7 +71,7 @@ mod tests {
                 nettype: Nettype::In,
                 addrtype: Addrtype::Ip4,
                 connection_address: ConnectionAddress {
-                    base: "233.252.0.1".parse().unwrap(),
+                    base: "198.51.100.1".parse::<IpAddr>().unwrap().into(),
                     ttl: None,
                     numaddr: None
                 }
@@ -77,7 +89,7 @@ mod tests {
                 nettype: Nettype::Other("In".into()),
                 addrtype: Addrtype::Other("Ip4".into()),
                 connection_address: ConnectionAddress {
-                    base: "233.252.0.1".parse().unwrap(),
+                    base: Address::Other("233.252.0.1".into()),
                     ttl: Some(127),
                     numaddr: Some(2)
                 }
@@ -85,13 +97,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_tokenizer3() {
+        let tokenizer: Tokenizer = ("PSTN", "E164", ("+441234567890")).into();
+
+        assert_eq!(
+            Connection::try_from(tokenizer),
+            Ok(Connection {
+                nettype: Nettype::Other("PSTN".into()),
+                addrtype: Addrtype::Other("E164".into()),
+                connection_address: ConnectionAddress {
+                    base: Address::Other("+441234567890".into()),
+                    ttl: None,
+                    numaddr: None
+                }
+            })
+        );
+    }
+
     #[test]
     fn display1() {
         let connection = Connection {
             nettype: Nettype::In,
             addrtype: Addrtype::Other("Ip4".into()),
             connection_address: ConnectionAddress {
-                base: "233.252.0.1".parse().unwrap(),
+                base: "233.252.0.1".parse::<IpAddr>().unwrap().into(),
                 ttl: Some(127),
                 numaddr: Some(2),
             },
@@ -4,54 +4,135 @@
 /// with it, unless you know what you are doing.
 pub use crate::tokenizers::value::Tokenizer;
 
-/// An email (`e=`) of SDP. Note that more than one such line could exist in an SDP
-/// message, that's why [crate::SessionDescription] has a `Vec<Email>` defined.
+use crate::{
+    lines::common::{split_display_name, DisplayName},
+    Error, ErrorKind,
+};
+use std::convert::TryFrom;
+
+/// An email (`e=`) of SDP, split into the email address and the optional display name. If the
+/// value can't be split, the whole (raw) value is kept as the address, which only
+/// [Email::validate] (hence strict parsing) rejects. Note that more than one such line could
+/// exist in an SDP message, that's why [crate::SessionDescription] has a `Vec<Email>` defined.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
-pub struct Email(String);
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
+pub struct Email {
+    pub address: String,
+    pub display_name: Option<DisplayName>,
+}
 
 impl Email {
-    pub fn new(email: String) -> Self {
-        Self(email)
+    pub fn new(address: String) -> Self {
+        Self {
+            address,
+            display_name: None,
+        }
     }
 
-    pub fn value(&self) -> &str {
-        &self.0
+    pub fn with_display_name(address: String, display_name: DisplayName) -> Self {
+        Self {
+            address,
+            display_name: Some(display_name),
+        }
     }
-}
 
-impl From<Email> for String {
-    fn from(email: Email) -> Self {
-        email.0
+    /// Checks that the email address is not empty and that it doesn't hold any whitespace or
+    /// leftovers of a display name (`<`, `>`, `(` or `)`).
+    pub fn validate(&self) -> Result<(), Error> {
+        let valid = !self.address.is_empty()
+            && !self
+                .address
+                .chars()
+                .any(|c| c.is_whitespace() || "<>()".contains(c));
+
+        match valid {
+            true => Ok(()),
+            false => Err(Error::parser_with_error(
+                ErrorKind::InvalidValue,
+                "email",
+                &self.address,
+                Error::new(ErrorKind::InvalidValue, "invalid email address".into()),
+            )),
+        }
     }
 }
 
-impl From<String> for Email {
-    fn from(email: String) -> Self {
-        Self(email)
+impl<'a> TryFrom<&'a str> for Email {
+    type Error = Error;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let (address, display_name) =
+            split_display_name("email", value).unwrap_or_else(|_| (value.into(), None));
+
+        Ok(Self {
+            address,
+            display_name,
+        })
     }
 }
 
-impl<'a> From<Tokenizer<'a, 'e'>> for Email {
-    fn from(tokenizer: Tokenizer<'a, 'e'>) -> Self {
-        Self(tokenizer.value.into())
+impl<'a> TryFrom<Tokenizer<'a, 'e'>> for Email {
+    type Error = Error;
+
+    fn try_from(tokenizer: Tokenizer<'a, 'e'>) -> Result<Self, Self::Error> {
+        Self::try_from(tokenizer.value)
     }
 }
 
 impl std::fmt::Display for Email {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "e={}", self.value())
+        write!(f, "e=")?;
+        match &self.display_name {
+            Some(display_name) => display_name.fmt_with(&self.address, f),
+            None => write!(f, "{}", self.address),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lines::common::DisplayNameSyntax;
 
     #[test]
     fn from_tokenizer1() {
         let tokenizer: Tokenizer<'e'> = "hello@televiska.com".into();
 
-        assert_eq!(Email::from(tokenizer), Email("hello@televiska.com".into()));
+        assert_eq!(
+            Email::try_from(tokenizer),
+            Ok(Email::new("hello@televiska.com".into()))
+        );
+    }
+
+    #[test]
+    fn from_tokenizer2() {
+        let tokenizer: Tokenizer<'e'> = "Televiska Support <hello@televiska.com>".into();
+
+        assert_eq!(
+            Email::try_from(tokenizer),
+            Ok(Email::with_display_name(
+                "hello@televiska.com".into(),
+                DisplayName::new("Televiska Support".into(), DisplayNameSyntax::Angle)
+            ))
+        );
+    }
+
+    #[test]
+    fn from_tokenizer3() {
+        for value in &["Alice <>", "x ()"] {
+            let tokenizer: Tokenizer<'e'> = (*value).into();
+            let email = Email::try_from(tokenizer).unwrap();
+
+            assert_eq!(email, Email::new((*value).into()));
+            assert!(email.validate().is_err());
+        }
+
+        let tokenizer: Tokenizer<'e'> = "Televiska Support <hello@televiska.com>".into();
+        assert!(Email::try_from(tokenizer).unwrap().validate().is_ok());
     }
 
     #[test]
@@ -60,4 +141,17 @@ mod tests {
 
         assert_eq!(email.to_string(), "e=hello@televiska.com");
     }
+
+    #[test]
+    fn display2() {
+        let email = Email::with_display_name(
+            "hello@televiska.com".into(),
+            DisplayName::new("Televiska Support".into(), DisplayNameSyntax::Comment),
+        );
+
+        assert_eq!(
+            email.to_string(),
+            "e=hello@televiska.com (Televiska Support)"
+        );
+    }
 }
@@ -1,6 +1,11 @@
 /// The key method as it appears in the key line (`k=`). It's not `Copy` type since it supports
 /// abstract types, not even defined in any RFC.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
 pub enum KeyMethod {
     Clear,
     Base64,
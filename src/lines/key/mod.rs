@@ -13,6 +13,11 @@ pub use key_method::KeyMethod;
 /// [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html) but might appear when working with
 /// legacy stuff.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Key {
     pub method: KeyMethod,
     pub encryption_key: String,
@@ -0,0 +1,206 @@
+use crate::{Error, ErrorKind};
+
+/// The formats (`fmt`) list of the media line (`m=`). For RTP based protos, formats are RTP
+/// payload types (0-127), while for any other proto (like `webrtc-datachannel`, `t38` or `*`)
+/// formats are kept as plain tokens.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
+pub enum Formats {
+    PayloadTypes(Vec<u8>),
+    Tokens(Vec<String>),
+}
+
+impl Formats {
+    /// Parses the space separated formats. If `rtp` is true, each format is expected to be an
+    /// RTP payload type.
+    pub fn parse(part: &str, rtp: bool) -> Result<Self, Error> {
+        let formats = part.split(' ').filter(|fmt| !fmt.is_empty());
+
+        match rtp {
+            true => Ok(Self::PayloadTypes(
+                formats
+                    .map(parse_payload_type)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+            false => Ok(Self::Tokens(formats.map(Into::into).collect())),
+        }
+    }
+
+    /// The payload types of the list. Empty for non-RTP formats.
+    pub fn payload_types(&self) -> &[u8] {
+        match self {
+            Self::PayloadTypes(payload_types) => payload_types,
+            Self::Tokens(_) => &[],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::PayloadTypes(payload_types) => payload_types.len(),
+            Self::Tokens(tokens) => tokens.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The position of the given format in the list.
+    pub fn position<T: std::fmt::Display>(&self, format: T) -> Option<usize> {
+        let format = format.to_string();
+
+        match self {
+            Self::PayloadTypes(payload_types) => {
+                let payload_type = format.parse::<u8>().ok()?;
+                payload_types.iter().position(|p| *p == payload_type)
+            }
+            Self::Tokens(tokens) => tokens.iter().position(|t| *t == format),
+        }
+    }
+
+    pub fn contains<T: std::fmt::Display>(&self, format: T) -> bool {
+        self.position(format).is_some()
+    }
+
+    /// Appends a format at the end of the list, unless it already exists. Fails if the list
+    /// holds payload types and the format is not a valid payload type.
+    pub fn add<T: std::fmt::Display>(&mut self, format: T) -> Result<(), Error> {
+        let format = format.to_string();
+        if self.contains(&format) {
+            return Ok(());
+        }
+
+        match self {
+            Self::PayloadTypes(payload_types) => payload_types.push(parse_payload_type(&format)?),
+            Self::Tokens(tokens) => tokens.push(format),
+        }
+
+        Ok(())
+    }
+
+    /// Removes a format from the list. Returns `true` if the format existed.
+    pub fn remove<T: std::fmt::Display>(&mut self, format: T) -> bool {
+        match self.position(format) {
+            Some(position) => {
+                match self {
+                    Self::PayloadTypes(payload_types) => {
+                        payload_types.remove(position);
+                    }
+                    Self::Tokens(tokens) => {
+                        tokens.remove(position);
+                    }
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the given formats to the beginning of the list, in the given order. Formats that
+    /// don't exist in the list are ignored, the rest of the formats keep their relative order.
+    pub fn reorder<T: std::fmt::Display>(&mut self, preferred: &[T]) {
+        for format in preferred.iter().rev() {
+            if let Some(position) = self.position(format) {
+                match self {
+                    Self::PayloadTypes(payload_types) => payload_types[..=position].rotate_right(1),
+                    Self::Tokens(tokens) => tokens[..=position].rotate_right(1),
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Formats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PayloadTypes(payload_types) => write!(
+                f,
+                "{}",
+                payload_types
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            Self::Tokens(tokens) => write!(f, "{}", tokens.join(" ")),
+        }
+    }
+}
+
+fn parse_payload_type(part: &str) -> Result<u8, Error> {
+    match part.parse::<u8>() {
+        Ok(payload_type) if payload_type <= 127 => Ok(payload_type),
+        Ok(_) => Err(Error::parser_with_error(
+            ErrorKind::InvalidValue,
+            "media payload type",
+            part,
+            Error::new(ErrorKind::InvalidValue, "out of range".into()),
+        )),
+        Err(e) => Err(Error::parser_with_error(
+            ErrorKind::InvalidNumber,
+            "media payload type",
+            part,
+            e,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse1() {
+        assert_eq!(
+            Formats::parse("0 8 96", true),
+            Ok(Formats::PayloadTypes(vec![0, 8, 96]))
+        );
+    }
+
+    #[test]
+    fn parse2() {
+        assert_eq!(
+            Formats::parse("webrtc-datachannel", false),
+            Ok(Formats::Tokens(vec!["webrtc-datachannel".into()]))
+        );
+    }
+
+    #[test]
+    fn parse3() {
+        assert!(Formats::parse("0 128", true).is_err());
+        assert!(Formats::parse("0 t38", true).is_err());
+    }
+
+    #[test]
+    fn add1() {
+        let mut formats = Formats::PayloadTypes(vec![0, 8]);
+        assert!(formats.add(96).is_ok());
+        assert!(formats.add(8).is_ok());
+        assert!(formats.add("foo").is_err());
+        assert_eq!(formats, Formats::PayloadTypes(vec![0, 8, 96]));
+    }
+
+    #[test]
+    fn remove1() {
+        let mut formats = Formats::Tokens(vec!["t38".into(), "*".into()]);
+        assert!(formats.remove("t38"));
+        assert!(!formats.remove("t38"));
+        assert_eq!(formats, Formats::Tokens(vec!["*".into()]));
+    }
+
+    #[test]
+    fn reorder1() {
+        let mut formats = Formats::PayloadTypes(vec![0, 8, 9, 96, 101]);
+        formats.reorder(&[96, 9, 120]);
+        assert_eq!(formats, Formats::PayloadTypes(vec![96, 9, 0, 8, 101]));
+    }
+
+    #[test]
+    fn display1() {
+        assert_eq!(Formats::PayloadTypes(vec![0, 8, 96]).to_string(), "0 8 96");
+    }
+}
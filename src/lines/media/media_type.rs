@@ -1,6 +1,11 @@
 /// The Media Type as it appears in the media line (`m=`). It's not a `Copy` type since it supports
 /// abstract types, not even defined in any RFC.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
 pub enum MediaType {
     Audio,
     Video,
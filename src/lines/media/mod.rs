@@ -1,47 +1,156 @@
 //! Types related to the media line (`m=`).
 
+mod formats;
 mod media_type;
 mod proto_type;
 
 /// The media line (`m=`) tokenizer. This is low level stuff and you shouldn't interact directly
 /// with it, unless you know what you are doing.
 pub use crate::tokenizers::media::Tokenizer;
+pub use formats::Formats;
 pub use media_type::MediaType;
-pub use proto_type::ProtoType;
+pub use proto_type::{Profile, ProtoType, RtpProfile, Security, Transport};
 
-use crate::Error;
+use crate::{
+    lines::{common::Address, Connection},
+    Error, ErrorKind,
+};
 use std::convert::TryFrom;
 
 /// A media line (`m=`) of SDP. Media is part of the high level [crate::MediaDescription] type.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Media {
     pub media: MediaType,
     pub port: u16,
-    pub num_of_ports: Option<u8>,
+    pub num_of_ports: Option<u16>,
     pub proto: ProtoType,
-    pub fmt: String,
+    pub formats: Formats,
+}
+
+impl Media {
+    /// The number of ports each layer of the media occupies: RTP uses a port pair (the odd port
+    /// being the RTCP one) while any other proto uses a single port.
+    fn ports_per_layer(&self) -> u16 {
+        match self.proto.is_rtp() {
+            true => 2,
+            false => 1,
+        }
+    }
+
+    /// The port of the last layer of the media, accounting for the port pairs of RTP protos but
+    /// not for the (implicit) RTCP port of the last one. Returns `None` if the port range goes
+    /// beyond `u16::MAX`.
+    fn last_port(&self) -> Option<u16> {
+        let last_port = u32::from(self.port)
+            + u32::from(self.num_of_ports.unwrap_or(1).max(1) - 1)
+                * u32::from(self.ports_per_layer());
+
+        u16::try_from(last_port).ok()
+    }
+
+    /// Works out the transport addresses of each layer of a hierarchically encoded media, as
+    /// `(address, RTP port, RTCP port)` tuples. Layers use consecutive ports (or port pairs for
+    /// RTP) of the media line and/or consecutive addresses of a multicast connection line, which,
+    /// when both are given, are mapped one-to-one. The RTCP port is always the one following the
+    /// RTP port, since an `a=rtcp` attribute can't be expressed per layer, and it's `None` for
+    /// non-RTP protos or when the RTP port is `u16::MAX`. Fails if the ports of the layers go
+    /// beyond `u16::MAX`.
+    pub fn transport_addresses(
+        &self,
+        connection: &Connection,
+    ) -> Result<Vec<(Address, u16, Option<u16>)>, Error> {
+        if self.last_port().is_none() {
+            return Err(Error::parser_with_error(
+                ErrorKind::InvalidValue,
+                "media port",
+                self.port,
+                Error::new(ErrorKind::InvalidValue, "port range overflows".into()),
+            ));
+        }
+
+        let addresses = match connection.connection_address.base {
+            Address::Ip(_) => connection
+                .connection_address
+                .addresses()
+                .map(Address::from)
+                .collect::<Vec<_>>(),
+            _ => vec![connection.connection_address.base.clone()],
+        };
+        let ports = self.num_of_ports.unwrap_or(1).max(1);
+        let layers = match (addresses.len(), usize::from(ports)) {
+            (addresses, ports) if addresses > 1 && ports > 1 => addresses.min(ports),
+            (addresses, ports) => addresses.max(ports),
+        };
+
+        //the port range has been checked above, so the layer ports can't overflow
+        Ok((0..layers)
+            .filter_map(|layer| {
+                let address = addresses.get(layer).or_else(|| addresses.first())?;
+                let port = match ports > 1 {
+                    true => self.port + layer as u16 * self.ports_per_layer(),
+                    false => self.port,
+                };
+                let rtcp_port = match self.proto.is_rtp() {
+                    true => port.checked_add(1),
+                    false => None,
+                };
+
+                Some((address.clone(), port, rtcp_port))
+            })
+            .collect())
+    }
 }
 
 impl<'a> TryFrom<Tokenizer<'a>> for Media {
     type Error = Error;
 
     fn try_from(tokenizer: Tokenizer<'a>) -> Result<Self, Self::Error> {
-        Ok(Self {
+        let proto: ProtoType = tokenizer.proto.into();
+
+        let media = Self {
             media: tokenizer.media.into(),
             port: tokenizer.port.port.parse().map_err(|e| {
-                Self::Error::parser_with_error("media port", tokenizer.port.port, e)
+                Self::Error::parser_with_error(
+                    ErrorKind::InvalidNumber,
+                    "media port",
+                    tokenizer.port.port,
+                    e,
+                )
             })?,
             num_of_ports: tokenizer
                 .port
                 .num_of_ports
                 .map(|num| {
-                    num.parse()
-                        .map_err(|e| Self::Error::parser_with_error("media num of ports", num, e))
+                    num.parse().map_err(|e| {
+                        Self::Error::parser_with_error(
+                            ErrorKind::InvalidNumber,
+                            "media num of ports",
+                            num,
+                            e,
+                        )
+                    })
                 })
                 .transpose()?,
-            proto: tokenizer.proto.into(),
-            fmt: tokenizer.fmt.into(),
-        })
+            formats: Formats::parse(tokenizer.fmt, proto.is_rtp())?,
+            proto,
+        };
+
+        //only an explicit port range can overflow, a single port is always valid
+        if media.num_of_ports.unwrap_or(1) > 1 && media.last_port().is_none() {
+            return Err(Self::Error::parser_with_error(
+                ErrorKind::InvalidValue,
+                "media port",
+                tokenizer.port.port,
+                Error::new(ErrorKind::InvalidValue, "port range overflows".into()),
+            ));
+        }
+
+        Ok(media)
     }
 }
 
@@ -51,12 +160,12 @@ impl std::fmt::Display for Media {
             Some(num_of_ports) => write!(
                 f,
                 "{} {}/{} {} {}",
-                self.media, self.port, num_of_ports, self.proto, self.fmt
+                self.media, self.port, num_of_ports, self.proto, self.formats
             ),
             None => write!(
                 f,
                 "{} {} {} {}",
-                self.media, self.port, self.proto, self.fmt
+                self.media, self.port, self.proto, self.formats
             ),
         }
     }
@@ -81,12 +190,143 @@ mod tests {
                 media: MediaType::Audio,
                 port: 49170,
                 num_of_ports: Some(2),
-                proto: ProtoType::RtpAvp,
-                fmt: "0".into(),
+                proto: "RTP/AVP".into(),
+                formats: Formats::PayloadTypes(vec![0]),
+            })
+        );
+    }
+
+    #[test]
+    fn from_tokenizer2() {
+        let tokenizer = Tokenizer {
+            media: "application",
+            port: "9".into(),
+            proto: "UDP/DTLS/SCTP",
+            fmt: "webrtc-datachannel",
+        };
+
+        assert_eq!(
+            Media::try_from(tokenizer),
+            Ok(Media {
+                media: MediaType::Application,
+                port: 9,
+                num_of_ports: None,
+                proto: ProtoType::new(Some(Transport::Udp), Some(Security::Dtls), Profile::Sctp),
+                formats: Formats::Tokens(vec!["webrtc-datachannel".into()]),
             })
         );
     }
 
+    #[test]
+    fn from_tokenizer3() {
+        let tokenizer = Tokenizer {
+            media: "audio",
+            port: "49170".into(),
+            proto: "RTP/AVP",
+            fmt: "0 200",
+        };
+
+        assert!(Media::try_from(tokenizer).is_err());
+    }
+
+    #[test]
+    fn from_tokenizer5() {
+        let tokenizer = Tokenizer {
+            media: "video",
+            port: ("65534", "2").into(),
+            proto: "RTP/AVP",
+            fmt: "31",
+        };
+
+        assert!(Media::try_from(tokenizer).is_err());
+    }
+
+    #[test]
+    fn transport_addresses1() {
+        let media = Media {
+            media: MediaType::Video,
+            port: 49170,
+            num_of_ports: Some(2),
+            proto: "RTP/AVP".into(),
+            formats: Formats::PayloadTypes(vec![31]),
+        };
+        let connection = Connection::try_from(crate::lines::connection::Tokenizer::from((
+            "IN",
+            "IP4",
+            ("224.2.1.1", "127", "2"),
+        )))
+        .unwrap();
+
+        assert_eq!(
+            media.transport_addresses(&connection),
+            Ok(vec![
+                (
+                    "224.2.1.1".parse::<std::net::IpAddr>().unwrap().into(),
+                    49170,
+                    Some(49171)
+                ),
+                (
+                    "224.2.1.2".parse::<std::net::IpAddr>().unwrap().into(),
+                    49172,
+                    Some(49173)
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn transport_addresses2() {
+        let media = Media {
+            media: MediaType::Video,
+            port: 49170,
+            num_of_ports: None,
+            proto: "RTP/AVP".into(),
+            formats: Formats::PayloadTypes(vec![31]),
+        };
+        let connection = Connection::try_from(crate::lines::connection::Tokenizer::from((
+            "IN",
+            "IP4",
+            ("224.2.1.1", "127", "3"),
+        )))
+        .unwrap();
+
+        assert_eq!(
+            media
+                .transport_addresses(&connection)
+                .unwrap()
+                .into_iter()
+                .map(|(_, port, _)| port)
+                .collect::<Vec<_>>(),
+            vec![49170, 49170, 49170]
+        );
+    }
+
+    #[test]
+    fn transport_addresses3() {
+        let tokenizer = Tokenizer {
+            media: "audio",
+            port: "65535".into(),
+            proto: "RTP/AVP",
+            fmt: "0",
+        };
+        let media = Media::try_from(tokenizer).unwrap();
+        let connection = Connection::try_from(crate::lines::connection::Tokenizer::from((
+            "IN",
+            "IP4",
+            "198.51.100.1",
+        )))
+        .unwrap();
+
+        assert_eq!(
+            media.transport_addresses(&connection),
+            Ok(vec![(
+                "198.51.100.1".parse::<std::net::IpAddr>().unwrap().into(),
+                65535,
+                None
+            )])
+        );
+    }
+
     #[test]
     fn display1() {
         assert_eq!(
@@ -94,8 +334,8 @@ mod tests {
                 media: MediaType::Audio,
                 port: 49170,
                 num_of_ports: Some(2),
-                proto: ProtoType::RtpAvp,
-                fmt: "0".into(),
+                proto: "RTP/AVP".into(),
+                formats: Formats::PayloadTypes(vec![0]),
             }
             .to_string(),
             "audio 49170/2 RTP/AVP 0"
@@ -1,33 +1,225 @@
-/// The Proto Type as it appears in the media line (`m=`). It's not a `Copy` type since it supports
-/// abstract types, not even defined in any RFC.
+use crate::parser_utils::split_once;
+
+/// The Proto Type as it appears in the media line (`m=`). It is modeled as a stack of layers,
+/// as registered in the IANA "proto" registry of
+/// [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html): an optional transport (`UDP`, `TCP`,
+/// `DCCP`), an optional security layer (`TLS`, `DTLS`) and the profile (`RTP/AVP`, `SCTP` etc).
+/// For instance `UDP/TLS/RTP/SAVPF` is `Udp` + `Tls` + `Rtp(Savpf)`. It's not a `Copy` type since
+/// it supports abstract profiles, not even defined in any RFC.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
+pub struct ProtoType {
+    pub transport: Option<Transport>,
+    pub security: Option<Security>,
+    pub profile: Profile,
+}
+
+/// The transport layer of the [ProtoType].
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
+pub enum Transport {
+    Udp,
+    Tcp,
+    Dccp,
+}
+
+/// The security layer of the [ProtoType].
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
+pub enum Security {
+    Tls,
+    Dtls,
+}
+
+/// The RTP profile of the [ProtoType], as in `RTP/<profile>`.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
+pub enum RtpProfile {
+    Avp,
+    Savp,
+    Avpf,
+    Savpf,
+}
+
+/// The profile (top) layer of the [ProtoType]. `Udp` refers to the plain `udp` proto of
+/// [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html).
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
-pub enum ProtoType {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
+pub enum Profile {
+    Rtp(RtpProfile),
+    Sctp,
+    Bfcp,
+    Msrp,
+    Udptl,
     Udp,
-    RtpAvp,
-    RtpSavp,
-    RtpSavpf,
     Other(String),
 }
 
+impl ProtoType {
+    pub fn new(transport: Option<Transport>, security: Option<Security>, profile: Profile) -> Self {
+        Self {
+            transport,
+            security,
+            profile,
+        }
+    }
+
+    /// Whether the proto is RTP based, in which case the media formats are RTP payload types.
+    pub fn is_rtp(&self) -> bool {
+        matches!(self.profile, Profile::Rtp(_))
+    }
+
+    /// Whether the media is secured, either by a security layer or by a secure RTP profile.
+    pub fn is_secure(&self) -> bool {
+        self.security.is_some()
+            || matches!(
+                self.profile,
+                Profile::Rtp(RtpProfile::Savp) | Profile::Rtp(RtpProfile::Savpf)
+            )
+    }
+
+    /// Whether the proto uses the RTCP feedback profile
+    /// ([RFC4585](https://www.rfc-editor.org/rfc/rfc4585.html)).
+    pub fn uses_feedback(&self) -> bool {
+        matches!(
+            self.profile,
+            Profile::Rtp(RtpProfile::Avpf) | Profile::Rtp(RtpProfile::Savpf)
+        )
+    }
+
+    /// Whether the proto uses DTLS. Note that `TLS` over a datagram transport (like in
+    /// `UDP/TLS/RTP/SAVPF`) means DTLS, as defined in
+    /// [RFC5764](https://www.rfc-editor.org/rfc/rfc5764.html).
+    pub fn is_dtls(&self) -> bool {
+        match self.security {
+            Some(Security::Dtls) => true,
+            Some(Security::Tls) => {
+                matches!(self.transport, Some(Transport::Udp) | Some(Transport::Dccp))
+            }
+            None => false,
+        }
+    }
+
+    /// Whether an offer with `self` proto can be answered with the `other` proto. RTP protos are
+    /// compatible when they share the same transport and the same security, regardless of the
+    /// feedback profile (AVPF falls back to AVP). SCTP based protos are all compatible with each
+    /// other, since the transport is negotiated by ICE. Any other proto must be identical.
+    pub fn is_compatible_with(&self, other: &Self) -> bool {
+        match (&self.profile, &other.profile) {
+            (Profile::Rtp(_), Profile::Rtp(_)) => {
+                self.transport.unwrap_or(Transport::Udp)
+                    == other.transport.unwrap_or(Transport::Udp)
+                    && self.is_secure() == other.is_secure()
+                    && self.is_dtls() == other.is_dtls()
+            }
+            (Profile::Sctp, Profile::Sctp) => self.is_dtls() == other.is_dtls(),
+            _ => self == other,
+        }
+    }
+}
+
 impl<'a> From<&'a str> for ProtoType {
+    fn from(from: &'a str) -> Self {
+        let (transport, layers) = match split_once(from, '/') {
+            Some(("UDP", rem)) => (Some(Transport::Udp), rem),
+            Some(("TCP", rem)) => (Some(Transport::Tcp), rem),
+            Some(("DCCP", rem)) => (Some(Transport::Dccp), rem),
+            _ => (None, from),
+        };
+        let (security, layers) = match split_once(layers, '/') {
+            Some(("TLS", rem)) => (Some(Security::Tls), rem),
+            Some(("DTLS", rem)) => (Some(Security::Dtls), rem),
+            _ => (None, layers),
+        };
+
+        Self {
+            transport,
+            security,
+            profile: layers.into(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Profile {
     fn from(from: &'a str) -> Self {
         match from {
+            s if s.eq("RTP/AVP") => Self::Rtp(RtpProfile::Avp),
+            s if s.eq("RTP/SAVP") => Self::Rtp(RtpProfile::Savp),
+            s if s.eq("RTP/AVPF") => Self::Rtp(RtpProfile::Avpf),
+            s if s.eq("RTP/SAVPF") => Self::Rtp(RtpProfile::Savpf),
+            s if s.eq("SCTP") => Self::Sctp,
+            s if s.eq("BFCP") => Self::Bfcp,
+            s if s.eq("MSRP") => Self::Msrp,
+            s if s.eq("udptl") => Self::Udptl,
             s if s.eq("udp") => Self::Udp,
-            s if s.eq("RTP/AVP") => Self::RtpAvp,
-            s if s.eq("RTP/SAVP") => Self::RtpSavp,
-            s if s.eq("RTP/SAVPF") => Self::RtpSavpf,
             s => Self::Other(s.into()),
         }
     }
 }
 
 impl std::fmt::Display for ProtoType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(transport) = self.transport {
+            write!(f, "{}/", transport)?;
+        }
+        if let Some(security) = self.security {
+            write!(f, "{}/", security)?;
+        }
+        write!(f, "{}", self.profile)
+    }
+}
+
+impl std::fmt::Display for Transport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Udp => write!(f, "UDP"),
+            Self::Tcp => write!(f, "TCP"),
+            Self::Dccp => write!(f, "DCCP"),
+        }
+    }
+}
+
+impl std::fmt::Display for Security {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::Tls => write!(f, "TLS"),
+            Self::Dtls => write!(f, "DTLS"),
+        }
+    }
+}
+
+impl std::fmt::Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rtp(RtpProfile::Avp) => write!(f, "RTP/AVP"),
+            Self::Rtp(RtpProfile::Savp) => write!(f, "RTP/SAVP"),
+            Self::Rtp(RtpProfile::Avpf) => write!(f, "RTP/AVPF"),
+            Self::Rtp(RtpProfile::Savpf) => write!(f, "RTP/SAVPF"),
+            Self::Sctp => write!(f, "SCTP"),
+            Self::Bfcp => write!(f, "BFCP"),
+            Self::Msrp => write!(f, "MSRP"),
+            Self::Udptl => write!(f, "udptl"),
             Self::Udp => write!(f, "udp"),
-            Self::RtpAvp => write!(f, "RTP/AVP"),
-            Self::RtpSavp => write!(f, "RTP/SAVP"),
-            Self::RtpSavpf => write!(f, "RTP/SAVPF"),
             Self::Other(inner) => write!(f, "{}", inner),
         }
     }
@@ -39,37 +231,114 @@ mod tests {
 
     #[test]
     fn from_str1() {
-        assert_eq!(ProtoType::from("udp"), ProtoType::Udp);
+        assert_eq!(
+            ProtoType::from("udp"),
+            ProtoType::new(None, None, Profile::Udp)
+        );
     }
 
     #[test]
     fn from_str2() {
-        assert_eq!(ProtoType::from("RTP/SAVPF"), ProtoType::RtpSavpf);
+        assert_eq!(
+            ProtoType::from("RTP/SAVPF"),
+            ProtoType::new(None, None, Profile::Rtp(RtpProfile::Savpf))
+        );
     }
 
     #[test]
     fn from_str3() {
         assert_eq!(
             ProtoType::from("rtp/savpf"),
-            ProtoType::Other("rtp/savpf".into())
+            ProtoType::new(None, None, Profile::Other("rtp/savpf".into()))
+        );
+    }
+
+    #[test]
+    fn from_str4() {
+        assert_eq!(
+            ProtoType::from("UDP/TLS/RTP/SAVPF"),
+            ProtoType::new(
+                Some(Transport::Udp),
+                Some(Security::Tls),
+                Profile::Rtp(RtpProfile::Savpf)
+            )
+        );
+    }
+
+    #[test]
+    fn from_str5() {
+        assert_eq!(
+            ProtoType::from("TCP/DTLS/SCTP"),
+            ProtoType::new(Some(Transport::Tcp), Some(Security::Dtls), Profile::Sctp)
+        );
+    }
+
+    #[test]
+    fn from_str6() {
+        assert_eq!(
+            ProtoType::from("TCP"),
+            ProtoType::new(None, None, Profile::Other("TCP".into()))
         );
     }
 
+    #[test]
+    fn predicates1() {
+        let proto = ProtoType::from("UDP/TLS/RTP/SAVPF");
+
+        assert!(proto.is_rtp());
+        assert!(proto.is_secure());
+        assert!(proto.uses_feedback());
+        assert!(proto.is_dtls());
+    }
+
+    #[test]
+    fn predicates2() {
+        let proto = ProtoType::from("RTP/AVP");
+
+        assert!(proto.is_rtp());
+        assert!(!proto.is_secure());
+        assert!(!proto.uses_feedback());
+        assert!(!proto.is_dtls());
+    }
+
+    #[test]
+    fn predicates3() {
+        let proto = ProtoType::from("TCP/TLS/RTP/SAVPF");
+
+        assert!(proto.is_secure());
+        assert!(!proto.is_dtls());
+    }
+
+    #[test]
+    fn compatible1() {
+        assert!(ProtoType::from("RTP/AVP").is_compatible_with(&"RTP/AVPF".into()));
+        assert!(!ProtoType::from("RTP/AVP").is_compatible_with(&"RTP/SAVP".into()));
+        assert!(!ProtoType::from("RTP/SAVPF").is_compatible_with(&"UDP/TLS/RTP/SAVPF".into()));
+        assert!(ProtoType::from("UDP/DTLS/SCTP").is_compatible_with(&"TCP/DTLS/SCTP".into()));
+        assert!(!ProtoType::from("udptl").is_compatible_with(&"RTP/AVP".into()));
+    }
+
     #[test]
     fn display1() {
-        assert_eq!(ProtoType::RtpSavpf.to_string(), "RTP/SAVPF");
+        assert_eq!(ProtoType::from("RTP/SAVPF").to_string(), "RTP/SAVPF");
     }
 
     #[test]
     fn display2() {
-        assert_eq!(ProtoType::Udp.to_string(), "udp");
+        assert_eq!(ProtoType::from("udp").to_string(), "udp");
     }
 
     #[test]
     fn display3() {
-        assert_eq!(
-            ProtoType::Other("something".into()).to_string(),
-            "something"
-        );
+        for proto in &[
+            "UDP/TLS/RTP/SAVPF",
+            "DCCP/TLS/RTP/SAVP",
+            "TCP/TLS/BFCP",
+            "UDP/BFCP",
+            "TCP/MSRP",
+            "something",
+        ] {
+            assert_eq!(ProtoType::from(*proto).to_string(), *proto);
+        }
     }
 }
@@ -1,10 +1,16 @@
 //! Types related to the origin line (`o=`).
 
 use crate::{
-    lines::common::{Addrtype, Nettype},
-    Error,
+    lines::common::{Address, Addrtype, Nettype},
+    Error, ErrorKind,
+};
+use std::{
+    collections::hash_map::RandomState,
+    convert::TryFrom,
+    hash::{BuildHasher, Hasher},
+    net::IpAddr,
+    time::SystemTime,
 };
-use std::{convert::TryFrom, net::IpAddr};
 
 /// The origin line (`o=`) tokenizer. This is low level stuff and you shouldn't interact directly
 /// with it, unless you know what you are doing.
@@ -12,28 +18,160 @@ pub use crate::tokenizers::origin::Tokenizer;
 
 /// The origin line (`o=`) of SDP.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Origin {
     pub username: String,
-    pub sess_id: String,
-    pub sess_version: String,
+    pub sess_id: SessionNumber,
+    pub sess_version: SessionNumber,
     pub nettype: Nettype,
     pub addrtype: Addrtype,
-    pub unicast_address: IpAddr,
+    pub unicast_address: Address,
+}
+
+/// The session id or the session version of the origin line (`o=`).
+/// [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html) requires both to be numeric and fit in
+/// 64 bits, but since some implementations don't respect that, anything else is kept as is in the
+/// `Other` variant.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", untagged)
+)]
+pub enum SessionNumber {
+    Number(u64),
+    Other(String),
+}
+
+impl SessionNumber {
+    pub fn number(&self) -> Option<u64> {
+        match self {
+            Self::Number(number) => Some(*number),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Increments the number. Fails, leaving the number as is, if the value is not numeric or
+    /// the incremented value would no longer fit in 64 bits, since RFC8866 requires both.
+    pub fn increment(&mut self) -> Result<(), Error> {
+        match self {
+            Self::Number(number) => match number.checked_add(1) {
+                Some(incremented) => {
+                    *number = incremented;
+                    Ok(())
+                }
+                None => Err(Error::parser_with_error(
+                    ErrorKind::InvalidNumber,
+                    "session number",
+                    number,
+                    Error::new(
+                        ErrorKind::InvalidValue,
+                        "can't be incremented beyond 64 bits".into(),
+                    ),
+                )),
+            },
+            Self::Other(other) => Err(Error::parser_with_error(
+                ErrorKind::InvalidNumber,
+                "session number",
+                other,
+                Error::new(
+                    ErrorKind::InvalidValue,
+                    "can't increment a non numeric (or not 64 bits) value".into(),
+                ),
+            )),
+        }
+    }
+}
+
+impl From<u64> for SessionNumber {
+    fn from(number: u64) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl<'a> From<&'a str> for SessionNumber {
+    fn from(part: &'a str) -> Self {
+        match part.parse() {
+            Ok(number) => Self::Number(number),
+            Err(_) => Self::Other(part.into()),
+        }
+    }
+}
+
+impl std::fmt::Display for SessionNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{}", number),
+            Self::Other(other) => write!(f, "{}", other),
+        }
+    }
+}
+
+impl Origin {
+    /// Creates a new origin for the given address, with `-` as the username. Following the
+    /// recommendation of [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html), the session id
+    /// and the session version are based on the current NTP timestamp. The low 32 bits of the
+    /// session id are random, so that sessions created in the same second don't clash.
+    pub fn new_random(unicast_address: IpAddr) -> Self {
+        let ntp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default()
+            + crate::lines::active::NTP_UNIX_OFFSET;
+        let random = RandomState::new().build_hasher().finish() & u64::from(u32::MAX);
+
+        Self {
+            username: "-".into(),
+            sess_id: ((ntp << 32) | random).into(),
+            sess_version: ntp.into(),
+            nettype: Nettype::In,
+            addrtype: match unicast_address {
+                IpAddr::V4(_) => Addrtype::Ip4,
+                IpAddr::V6(_) => Addrtype::Ip6,
+            },
+            unicast_address: unicast_address.into(),
+        }
+    }
+
+    /// Whether both origins refer to the same session, in which case a description with this
+    /// origin is a new version of the session (like a re-offer) and not a new session. As
+    /// defined in [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html), all fields apart
+    /// from the session version form the globally unique identifier of the session.
+    pub fn is_same_session(&self, other: &Self) -> bool {
+        self.username == other.username
+            && self.sess_id == other.sess_id
+            && self.nettype == other.nettype
+            && self.addrtype == other.addrtype
+            && self.unicast_address == other.unicast_address
+    }
 }
 
 impl<'a> TryFrom<Tokenizer<'a>> for Origin {
     type Error = Error;
 
     fn try_from(tokenizer: Tokenizer<'a>) -> Result<Self, Self::Error> {
+        let addrtype = tokenizer.addrtype.into();
+
         Ok(Self {
             username: tokenizer.username.into(),
             sess_id: tokenizer.sess_id.into(),
             sess_version: tokenizer.sess_version.into(),
             nettype: tokenizer.nettype.into(),
-            addrtype: tokenizer.addrtype.into(),
-            unicast_address: tokenizer.unicast_address.parse().map_err(|e| {
-                Self::Error::parser_with_error("origin ip address", tokenizer.unicast_address, e)
-            })?,
+            unicast_address: Address::try_from((tokenizer.unicast_address, &addrtype)).map_err(
+                |e| {
+                    Self::Error::parser_with_error(
+                        ErrorKind::InvalidAddress,
+                        "origin address",
+                        tokenizer.unicast_address,
+                        e,
+                    )
+                },
+            )?,
+            addrtype,
         })
     }
 }
@@ -56,7 +194,7 @@ impl std::fmt::Display for Origin {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::net::Ipv4Addr;
+    use std::net::{IpAddr, Ipv4Addr};
 
     #[test]
     fn from_tokenizer1() {
@@ -73,11 +211,11 @@ mod tests {
             Origin::try_from(tokenizer),
             Ok(Origin {
                 username: "jdoe".into(),
-                sess_id: "3724394400".into(),
-                sess_version: "3724394405".into(),
+                sess_id: 3724394400.into(),
+                sess_version: 3724394405.into(),
                 nettype: Nettype::In,
                 addrtype: Addrtype::Ip4,
-                unicast_address: IpAddr::V4(Ipv4Addr::new(198, 51, 100, 1)),
+                unicast_address: IpAddr::V4(Ipv4Addr::new(198, 51, 100, 1)).into(),
             })
         );
     }
@@ -90,21 +228,45 @@ mod tests {
             sess_version: "3724394405",
             nettype: "IN",
             addrtype: "IP4",
-            unicast_address: "noip",
+            unicast_address: "no_ip",
         };
 
         assert!(Origin::try_from(tokenizer).is_err());
     }
 
+    #[test]
+    fn from_tokenizer3() {
+        let tokenizer = Tokenizer {
+            username: "-",
+            sess_id: "1",
+            sess_version: "1",
+            nettype: "IN",
+            addrtype: "IP4",
+            unicast_address: "host.example.com",
+        };
+
+        assert_eq!(
+            Origin::try_from(tokenizer),
+            Ok(Origin {
+                username: "-".into(),
+                sess_id: 1.into(),
+                sess_version: 1.into(),
+                nettype: Nettype::In,
+                addrtype: Addrtype::Ip4,
+                unicast_address: Address::Fqdn("host.example.com".into()),
+            })
+        );
+    }
+
     #[test]
     fn display1() {
         let origin = Origin {
             username: "jdoe".into(),
-            sess_id: "3724394400".into(),
-            sess_version: "3724394405".into(),
+            sess_id: 3724394400.into(),
+            sess_version: 3724394405.into(),
             nettype: Nettype::In,
             addrtype: Addrtype::Ip4,
-            unicast_address: IpAddr::V4(Ipv4Addr::new(198, 51, 100, 1)),
+            unicast_address: IpAddr::V4(Ipv4Addr::new(198, 51, 100, 1)).into(),
         };
 
         assert_eq!(
@@ -112,4 +274,66 @@ mod tests {
             "o=jdoe 3724394400 3724394405 IN IP4 198.51.100.1"
         );
     }
+
+    #[test]
+    fn from_tokenizer4() {
+        let tokenizer = Tokenizer {
+            username: "-",
+            sess_id: "36893488147419103232",
+            sess_version: "v1",
+            nettype: "IN",
+            addrtype: "IP4",
+            unicast_address: "198.51.100.1",
+        };
+
+        let origin = Origin::try_from(tokenizer).unwrap();
+        assert_eq!(
+            origin.sess_id,
+            SessionNumber::Other("36893488147419103232".into())
+        );
+        assert_eq!(origin.sess_version, SessionNumber::Other("v1".into()));
+    }
+
+    #[test]
+    fn increment1() {
+        let mut number = SessionNumber::Number(u64::MAX);
+        assert!(number.increment().is_err());
+        assert_eq!(number, SessionNumber::Number(u64::MAX));
+
+        let mut number = SessionNumber::Other("v1".into());
+        assert!(number.increment().is_err());
+        assert_eq!(number, SessionNumber::Other("v1".into()));
+
+        let mut number = SessionNumber::Number(41);
+        assert_eq!(number.increment(), Ok(()));
+        assert_eq!(number, SessionNumber::Number(42));
+    }
+
+    #[test]
+    fn new_random1() {
+        let address = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 1));
+        let origin = Origin::new_random(address);
+
+        assert_eq!(origin.addrtype, Addrtype::Ip4);
+        assert!(origin.sess_version.number().unwrap() > crate::lines::active::NTP_UNIX_OFFSET);
+        assert!(!origin.is_same_session(&Origin::new_random(address)));
+    }
+
+    #[test]
+    fn is_same_session1() {
+        let origin = Origin {
+            username: "jdoe".into(),
+            sess_id: 3724394400.into(),
+            sess_version: 3724394405.into(),
+            nettype: Nettype::In,
+            addrtype: Addrtype::Ip4,
+            unicast_address: IpAddr::V4(Ipv4Addr::new(198, 51, 100, 1)).into(),
+        };
+        let mut reoffer = origin.clone();
+        reoffer.sess_version.increment().unwrap();
+
+        assert!(origin.is_same_session(&reoffer));
+        reoffer.sess_id = 1.into();
+        assert!(!origin.is_same_session(&reoffer));
+    }
 }
@@ -4,54 +4,137 @@
 /// with it, unless you know what you are doing.
 pub use crate::tokenizers::value::Tokenizer;
 
-/// A phone number line (`p=`) of SDP. Note that more than one such line could exist in an SDP
-/// message, that's why [crate::SessionDescription] has a `Vec<Phone>` defined.
+use crate::{
+    lines::common::{split_display_name, DisplayName},
+    Error, ErrorKind,
+};
+use std::convert::TryFrom;
+
+/// A phone number line (`p=`) of SDP, split into the phone number and the optional display name.
+/// If the value can't be split, the whole (raw) value is kept as the phone number. The phone
+/// number is checked against the character set of the
+/// [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html) ABNF only by [Phone::validate], which
+/// strict parsing does. Note that more than one such line could exist in an SDP message, that's
+/// why [crate::SessionDescription] has a `Vec<Phone>` defined.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
-pub struct Phone(String);
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
+pub struct Phone {
+    pub number: String,
+    pub display_name: Option<DisplayName>,
+}
 
 impl Phone {
-    pub fn new(phone: String) -> Self {
-        Self(phone)
+    pub fn new(number: String) -> Self {
+        Self {
+            number,
+            display_name: None,
+        }
     }
 
-    pub fn value(&self) -> &str {
-        &self.0
+    pub fn with_display_name(number: String, display_name: DisplayName) -> Self {
+        Self {
+            number,
+            display_name: Some(display_name),
+        }
     }
-}
 
-impl From<Phone> for String {
-    fn from(phone: Phone) -> Self {
-        phone.0
+    /// Checks that the phone number follows the RFC8866 ABNF: an optional `+`, followed by a
+    /// digit and then digits, spaces or dashes (like `+1 617 555-6011`).
+    pub fn validate(&self) -> Result<(), Error> {
+        let digits = self.number.strip_prefix('+').unwrap_or(&self.number);
+        let valid = digits.starts_with(|c: char| c.is_ascii_digit())
+            && digits
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == ' ' || c == '-');
+
+        match valid {
+            true => Ok(()),
+            false => Err(Error::parser_with_error(
+                ErrorKind::InvalidValue,
+                "phone",
+                &self.number,
+                Error::new(ErrorKind::InvalidValue, "invalid phone number".into()),
+            )),
+        }
     }
 }
 
-impl From<String> for Phone {
-    fn from(phone: String) -> Self {
-        Self(phone)
+impl<'a> TryFrom<&'a str> for Phone {
+    type Error = Error;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let (number, display_name) =
+            split_display_name("phone", value).unwrap_or_else(|_| (value.into(), None));
+
+        Ok(Self {
+            number,
+            display_name,
+        })
     }
 }
 
-impl<'a> From<Tokenizer<'a, 'p'>> for Phone {
-    fn from(tokenizer: Tokenizer<'a, 'p'>) -> Self {
-        Self(tokenizer.value.into())
+impl<'a> TryFrom<Tokenizer<'a, 'p'>> for Phone {
+    type Error = Error;
+
+    fn try_from(tokenizer: Tokenizer<'a, 'p'>) -> Result<Self, Self::Error> {
+        Self::try_from(tokenizer.value)
     }
 }
 
 impl std::fmt::Display for Phone {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "p={}", self.value())
+        write!(f, "p=")?;
+        match &self.display_name {
+            Some(display_name) => display_name.fmt_with(&self.number, f),
+            None => write!(f, "{}", self.number),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lines::common::DisplayNameSyntax;
 
     #[test]
     fn from_tokenizer1() {
         let tokenizer: Tokenizer<'p'> = "+1234567890".into();
 
-        assert_eq!(Phone::from(tokenizer), Phone("+1234567890".into()));
+        assert_eq!(
+            Phone::try_from(tokenizer),
+            Ok(Phone::new("+1234567890".into()))
+        );
+    }
+
+    #[test]
+    fn from_tokenizer2() {
+        let tokenizer: Tokenizer<'p'> = "+1 617 555-6011 (Jane Doe)".into();
+
+        assert_eq!(
+            Phone::try_from(tokenizer),
+            Ok(Phone::with_display_name(
+                "+1 617 555-6011".into(),
+                DisplayName::new("Jane Doe".into(), DisplayNameSyntax::Comment)
+            ))
+        );
+    }
+
+    #[test]
+    fn from_tokenizer3() {
+        let tokenizer: Tokenizer<'p'> = "(555) 123 4567".into();
+        let phone = Phone::try_from(tokenizer).unwrap();
+        assert_eq!(phone, Phone::new("(555) 123 4567".into()));
+        assert!(phone.validate().is_err());
+
+        let tokenizer: Tokenizer<'p'> = "+-1234".into();
+        assert!(Phone::try_from(tokenizer).unwrap().validate().is_err());
+
+        let tokenizer: Tokenizer<'p'> = "+1 617 555-6011 (Jane Doe)".into();
+        assert!(Phone::try_from(tokenizer).unwrap().validate().is_ok());
     }
 
     #[test]
@@ -60,4 +143,14 @@ mod tests {
 
         assert_eq!(phone.to_string(), "p=+1234567890");
     }
+
+    #[test]
+    fn display2() {
+        let phone = Phone::with_display_name(
+            "+1 617 555-6011".into(),
+            DisplayName::new("Jane Doe".into(), DisplayNameSyntax::Angle),
+        );
+
+        assert_eq!(phone.to_string(), "p=Jane Doe <+1 617 555-6011>");
+    }
 }
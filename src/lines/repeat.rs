@@ -1,33 +1,99 @@
 //! Types related to the repeat line (`r=`).
 
-use crate::Error;
+use crate::{Error, ErrorKind};
 use std::convert::{TryFrom, TryInto};
 
 /// The repeat line (`r=`) tokenizer. This is low level stuff and you shouldn't interact directly
 /// with it, unless you know what you are doing.
 pub use crate::tokenizers::time::repeat::Tokenizer;
 
-/// The repeat line (`r=`) of SDP.
 use crate::lines::common::TypedTime;
+use chrono::Duration;
 
 /// The repeat time (`r=`) of SDP.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Repeat {
     pub interval: TypedTime,
     pub duration: TypedTime,
     pub offsets: Vec<TypedTime>,
 }
 
+impl Repeat {
+    /// Converts the interval, duration and offsets to their compact form (like `r=7d 1h 0 25h`),
+    /// see [TypedTime::compact].
+    pub fn normalize(&mut self) {
+        self.interval = self.interval.normalize();
+        self.duration = self.duration.normalize();
+        self.offsets.iter_mut().for_each(|o| *o = o.normalize());
+    }
+
+    /// Checks that the interval, the duration and the offsets are not negative and that the
+    /// duration is not longer than the interval. Parsing only does that in
+    /// [crate::Strictness::Strict] mode.
+    pub fn validate(&self) -> Result<(), Error> {
+        let interval = Duration::from(self.interval);
+        let duration = Duration::from(self.duration);
+
+        if interval < Duration::zero() {
+            return Err(Error::parser_with_error(
+                ErrorKind::InvalidTime,
+                "repeat interval",
+                self.interval,
+                Error::new(ErrorKind::InvalidValue, "negative interval".into()),
+            ));
+        }
+        if duration < Duration::zero() || duration > interval {
+            return Err(Error::parser_with_error(
+                ErrorKind::InvalidTime,
+                "repeat duration",
+                self.duration,
+                Error::new(
+                    ErrorKind::InvalidValue,
+                    "negative or longer than the interval".into(),
+                ),
+            ));
+        }
+        match self
+            .offsets
+            .iter()
+            .find(|o| Duration::from(**o) < Duration::zero())
+        {
+            Some(offset) => Err(Error::parser_with_error(
+                ErrorKind::InvalidTime,
+                "repeat offset",
+                offset,
+                Error::new(ErrorKind::InvalidValue, "negative offset".into()),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
 impl<'a> TryFrom<Tokenizer<'a>> for Repeat {
     type Error = Error;
 
     fn try_from(tokenizer: Tokenizer<'a>) -> Result<Self, Self::Error> {
         Ok(Self {
             interval: tokenizer.interval.try_into().map_err(|e| {
-                Self::Error::parser_with_error("repeat interval", tokenizer.interval, e)
+                Self::Error::parser_with_error(
+                    ErrorKind::InvalidTime,
+                    "repeat interval",
+                    tokenizer.interval,
+                    e,
+                )
             })?,
             duration: tokenizer.duration.try_into().map_err(|e| {
-                Self::Error::parser_with_error("repeat duration", tokenizer.duration, e)
+                Self::Error::parser_with_error(
+                    ErrorKind::InvalidTime,
+                    "repeat duration",
+                    tokenizer.duration,
+                    e,
+                )
             })?,
             offsets: tokenizer
                 .offsets
@@ -57,7 +123,6 @@ impl std::fmt::Display for Repeat {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Duration;
 
     #[test]
     fn from_tokenizer1() {
@@ -93,6 +158,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_tokenizer3() {
+        let tokenizer: Tokenizer = ("-7d", "1h", vec!["0"]).into();
+        assert!(Repeat::try_from(tokenizer).unwrap().validate().is_err());
+
+        let tokenizer: Tokenizer = ("1h", "2h", vec!["0"]).into();
+        assert!(Repeat::try_from(tokenizer).unwrap().validate().is_err());
+
+        let tokenizer: Tokenizer = ("1d", "1h", vec!["-1h"]).into();
+        assert!(Repeat::try_from(tokenizer).unwrap().validate().is_err());
+
+        let tokenizer: Tokenizer = ("1d", "1h", vec!["0"]).into();
+        assert!(Repeat::try_from(tokenizer).unwrap().validate().is_ok());
+    }
+
+    #[test]
+    fn normalize1() {
+        let mut repeat = Repeat {
+            interval: TypedTime::None(Duration::seconds(604800)),
+            duration: TypedTime::Seconds(Duration::seconds(3600)),
+            offsets: vec![
+                TypedTime::Hours(Duration::seconds(0)),
+                TypedTime::None(Duration::seconds(90000)),
+            ],
+        };
+        repeat.normalize();
+
+        assert_eq!(repeat.to_string(), "r=7d 1h 0 25h");
+    }
+
     #[test]
     fn display1() {
         let repeat = Repeat {
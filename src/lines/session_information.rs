@@ -6,33 +6,70 @@ pub use crate::tokenizers::value::Tokenizer;
 
 /// The session information line (`i=`) of SDP.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
-pub struct SessionInformation(String);
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
+pub struct SessionInformation {
+    value: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    raw: Option<Vec<u8>>,
+}
 
 impl SessionInformation {
     pub fn new(session_information: String) -> Self {
-        Self(session_information)
+        Self {
+            value: session_information,
+            raw: None,
+        }
+    }
+
+    /// Creates it out of raw bytes, decoding them with the given charset (as declared by
+    /// `a=charset`), or as UTF-8 if there is none. If the value doesn't match the raw bytes
+    /// (because they are not UTF-8), the raw bytes are kept as well.
+    pub fn from_bytes(bytes: &[u8], charset: Option<&str>) -> Self {
+        let value = crate::lines::common::decode_text(bytes, charset);
+
+        Self {
+            raw: match value.as_bytes() == bytes {
+                true => None,
+                false => Some(bytes.into()),
+            },
+            value,
+        }
     }
 
+    /// The (decoded) value.
     pub fn value(&self) -> &str {
-        &self.0
+        &self.value
+    }
+
+    /// The raw bytes of the value, only if it was created out of bytes that are not the UTF-8
+    /// encoding of the value.
+    pub fn raw(&self) -> Option<&[u8]> {
+        self.raw.as_deref()
     }
 }
 
 impl From<SessionInformation> for String {
     fn from(session_information: SessionInformation) -> Self {
-        session_information.0
+        session_information.value
     }
 }
 
 impl From<String> for SessionInformation {
     fn from(session_information: String) -> Self {
-        Self(session_information)
+        Self::new(session_information)
     }
 }
 
 impl<'a> From<Tokenizer<'a, 'i'>> for SessionInformation {
     fn from(tokenizer: Tokenizer<'a, 'i'>) -> Self {
-        Self(tokenizer.value.into())
+        Self::new(tokenizer.value.into())
     }
 }
 
@@ -52,7 +89,7 @@ mod tests {
 
         assert_eq!(
             SessionInformation::from(tokenizer),
-            SessionInformation("a value".into())
+            SessionInformation::new("a value".into())
         );
     }
 
@@ -6,33 +6,70 @@ pub use crate::tokenizers::value::Tokenizer;
 
 /// The session name line (`s=`) of SDP.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
-pub struct SessionName(String);
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
+pub struct SessionName {
+    value: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    raw: Option<Vec<u8>>,
+}
 
 impl SessionName {
     pub fn new(session_name: String) -> Self {
-        Self(session_name)
+        Self {
+            value: session_name,
+            raw: None,
+        }
+    }
+
+    /// Creates it out of raw bytes, decoding them with the given charset (as declared by
+    /// `a=charset`), or as UTF-8 if there is none. If the value doesn't match the raw bytes
+    /// (because they are not UTF-8), the raw bytes are kept as well.
+    pub fn from_bytes(bytes: &[u8], charset: Option<&str>) -> Self {
+        let value = crate::lines::common::decode_text(bytes, charset);
+
+        Self {
+            raw: match value.as_bytes() == bytes {
+                true => None,
+                false => Some(bytes.into()),
+            },
+            value,
+        }
     }
 
+    /// The (decoded) value.
     pub fn value(&self) -> &str {
-        &self.0
+        &self.value
+    }
+
+    /// The raw bytes of the value, only if it was created out of bytes that are not the UTF-8
+    /// encoding of the value.
+    pub fn raw(&self) -> Option<&[u8]> {
+        self.raw.as_deref()
     }
 }
 
 impl From<SessionName> for String {
     fn from(session_name: SessionName) -> Self {
-        session_name.0
+        session_name.value
     }
 }
 
 impl From<String> for SessionName {
     fn from(session_name: String) -> Self {
-        Self(session_name)
+        Self::new(session_name)
     }
 }
 
 impl<'a> From<Tokenizer<'a, 's'>> for SessionName {
     fn from(tokenizer: Tokenizer<'a, 's'>) -> Self {
-        Self(tokenizer.value.into())
+        Self::new(tokenizer.value.into())
     }
 }
 
@@ -50,7 +87,10 @@ mod tests {
     fn from_tokenizer1() {
         let tokenizer: Tokenizer<'s'> = "a value".into();
 
-        assert_eq!(SessionName::from(tokenizer), SessionName("a value".into()));
+        assert_eq!(
+            SessionName::from(tokenizer),
+            SessionName::new("a value".into())
+        );
     }
 
     #[test]
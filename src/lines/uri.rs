@@ -4,29 +4,51 @@
 /// with it, unless you know what you are doing.
 pub use crate::tokenizers::value::Tokenizer;
 
-/// The uri line (`u=`) of SDP. It is not parsed, you can use `value` to
-/// get the actual value and parse it as a proper URI.
+use crate::{lines::common::UriReference, Error};
+
+/// The uri line (`u=`) of SDP. The URI is split into its components (see [UriReference]) but it
+/// is not validated, you can use [Uri::validate] for that. `value` returns the original text.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
-pub struct Uri(String);
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
+pub struct Uri(UriReference);
 
 impl Uri {
     pub fn new(uri: String) -> Self {
-        Self(uri)
+        Self(uri.into())
     }
 
     pub fn value(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn uri(&self) -> &UriReference {
         &self.0
     }
+
+    /// Checks that the uri is a valid URI reference, see [UriReference::validate].
+    pub fn validate(&self) -> Result<(), Error> {
+        self.0.validate()
+    }
 }
 
 impl From<Uri> for String {
     fn from(uri: Uri) -> Self {
-        uri.0
+        uri.0.into()
     }
 }
 
 impl From<String> for Uri {
     fn from(uri: String) -> Self {
+        Self(uri.into())
+    }
+}
+
+impl From<UriReference> for Uri {
+    fn from(uri: UriReference) -> Self {
         Self(uri)
     }
 }
@@ -51,10 +73,21 @@ mod tests {
     fn from_tokenizer1() {
         let tokenizer: Tokenizer<'u'> = "http://www.jdoe.example.com/home.html".into();
 
+        let uri = Uri::from(tokenizer);
         assert_eq!(
-            Uri::from(tokenizer),
-            Uri("http://www.jdoe.example.com/home.html".into())
+            uri,
+            Uri::new("http://www.jdoe.example.com/home.html".into())
         );
+        assert_eq!(uri.uri().authority(), Some("www.jdoe.example.com"));
+    }
+
+    #[test]
+    fn from_tokenizer2() {
+        let tokenizer: Tokenizer<'u'> = "not a uri".into();
+
+        let uri = Uri::from(tokenizer);
+        assert_eq!(uri.value(), "not a uri");
+        assert!(uri.validate().is_err());
     }
 
     #[test]
@@ -1,6 +1,6 @@
 //! Types related to the version line (`v=`).
 
-use crate::Error;
+use crate::{Error, ErrorKind};
 use std::convert::TryFrom;
 
 /// The version line (`v=`) tokenizer. This is low level stuff and you shouldn't interact directly
@@ -9,6 +9,11 @@ pub use crate::tokenizers::value::Tokenizer;
 
 /// The version line (`v=`) of SDP. It is expected to always be 0.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde", rename_all = "snake_case")
+)]
 pub enum Version {
     V0,
 }
@@ -20,7 +25,11 @@ impl<'a> TryFrom<Tokenizer<'a, 'v'>> for Version {
         if tokenizer.value.eq("0") {
             Ok(Self::V0)
         } else {
-            Err(Error::parser("version", tokenizer.value))
+            Err(Error::parser(
+                ErrorKind::InvalidValue,
+                "version",
+                tokenizer.value,
+            ))
         }
     }
 }
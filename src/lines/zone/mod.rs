@@ -12,10 +12,25 @@ use std::convert::{TryFrom, TryInto};
 
 /// A zone line (`z=`) of SDP. It holds all the repeat zone adjustments in a `Vector`.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Zone {
     pub parts: Vec<ZonePart>,
 }
 
+impl Zone {
+    /// Converts the offsets to their compact form (like `z=3730928400 -1h`), see
+    /// [crate::lines::common::TypedTime::compact].
+    pub fn normalize(&mut self) {
+        self.parts
+            .iter_mut()
+            .for_each(|part| part.offset = part.offset.normalize());
+    }
+}
+
 impl<'a> TryFrom<Tokenizer<'a>> for Zone {
     type Error = Error;
 
@@ -71,6 +86,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn normalize1() {
+        let mut zone = Zone {
+            parts: vec![
+                ZonePart {
+                    adjustment_time: 3730928400,
+                    offset: TypedTime::None(Duration::hours(-1)),
+                },
+                ZonePart {
+                    adjustment_time: 3749680800,
+                    offset: TypedTime::Minutes(Duration::hours(0)),
+                },
+            ],
+        };
+        zone.normalize();
+
+        assert_eq!(zone.to_string(), "z=3730928400 -1h 3749680800 0");
+    }
+
     #[test]
     fn display1() {
         let zone = Zone {
@@ -1,4 +1,4 @@
-use crate::{lines::common::TypedTime, Error};
+use crate::{lines::common::TypedTime, Error, ErrorKind};
 use std::convert::{TryFrom, TryInto};
 
 pub use crate::tokenizers::time::zone_part::Tokenizer;
@@ -6,6 +6,11 @@ pub use crate::tokenizers::time::zone_part::Tokenizer;
 /// A zone part is part of the zone line ([super::Zone]). It holds the adjustment time and the
 /// offset of that adjustment time.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct ZonePart {
     pub adjustment_time: u64,
     pub offset: TypedTime,
@@ -17,12 +22,21 @@ impl<'a> TryFrom<Tokenizer<'a>> for ZonePart {
     fn try_from(tokenizer: Tokenizer<'a>) -> Result<Self, Self::Error> {
         Ok(Self {
             adjustment_time: tokenizer.adjustment.parse().map_err(|e| {
-                Self::Error::parser_with_error("zone adjustment time", tokenizer.adjustment, e)
+                Self::Error::parser_with_error(
+                    ErrorKind::InvalidTime,
+                    "zone adjustment time",
+                    tokenizer.adjustment,
+                    e,
+                )
+            })?,
+            offset: tokenizer.offset.try_into().map_err(|e| {
+                Self::Error::parser_with_error(
+                    ErrorKind::InvalidTime,
+                    "zone offset",
+                    tokenizer.offset,
+                    e,
+                )
             })?,
-            offset: tokenizer
-                .offset
-                .try_into()
-                .map_err(|e| Self::Error::parser_with_error("zone offset", tokenizer.offset, e))?,
         })
     }
 }
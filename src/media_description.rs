@@ -1,5 +1,5 @@
-use crate::{lines, Error};
-use std::convert::{TryFrom, TryInto};
+use crate::{error::located, lines, Error};
+use std::convert::TryFrom;
 
 /// The Media description high level type tokenizer. It tokenizes all lines related to a Media
 /// description.
@@ -10,6 +10,11 @@ pub use crate::tokenizers::media_description::Tokenizer;
 /// The Media description high level type. This type holds all types related to a complete Media
 /// description (info, connections, bandwidths, attributes etc).
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct MediaDescription {
     pub media: lines::Media,
     pub info: Option<lines::SessionInformation>,
@@ -19,28 +24,181 @@ pub struct MediaDescription {
     pub attributes: Vec<lines::Attribute>,
 }
 
+impl MediaDescription {
+    /// The direction of this media description. Following
+    /// [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html), a media level direction attribute
+    /// overrides the session level one (usually [crate::SessionDescription::direction]).
+    pub fn direction(
+        &self,
+        session_direction: lines::attribute::Direction,
+    ) -> lines::attribute::Direction {
+        lines::attribute::Direction::find_in(&self.attributes).unwrap_or(session_direction)
+    }
+
+    /// Sets the media level direction, replacing any existing direction attribute.
+    pub fn set_direction(&mut self, direction: lines::attribute::Direction) {
+        direction.replace_in(&mut self.attributes)
+    }
+
+    /// Removes the given payload type from the media line, along with its `rtpmap`, `fmtp` and
+    /// `rtcp-fb` attributes. Returns `true` if the payload type existed in the media line.
+    pub fn remove_payload_type(&mut self, payload_type: u8) -> bool {
+        use lines::Attribute;
+
+        let fmt = payload_type.to_string();
+        self.attributes.retain(|attribute| match attribute {
+            Attribute::Rtpmap(rtpmap) => rtpmap.payload_type != payload_type,
+            Attribute::Fmtp(fmtp) => fmtp.fmt != fmt,
+            Attribute::Other(key, Some(value)) if key.eq("rtcp-fb") => {
+                value.split(' ').next() != Some(fmt.as_str())
+            }
+            _ => true,
+        });
+
+        self.media.formats.remove(payload_type)
+    }
+
+    /// The `rtpmap` of the given payload type. If there is no `rtpmap` attribute for it, the
+    /// static payload types table of [RFC3551](https://www.rfc-editor.org/rfc/rfc3551.html) is
+    /// used instead.
+    pub fn rtpmap(&self, payload_type: u8) -> Option<lines::attribute::Rtpmap> {
+        self.attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                lines::Attribute::Rtpmap(rtpmap) if rtpmap.payload_type == payload_type => {
+                    Some(rtpmap.clone())
+                }
+                _ => None,
+            })
+            .or_else(|| lines::attribute::Rtpmap::from_static(payload_type))
+    }
+
+    /// The codec (encoding name) of the given payload type, see [MediaDescription::rtpmap].
+    pub fn codec(&self, payload_type: u8) -> Option<lines::attribute::EncodingName> {
+        self.rtpmap(payload_type).map(|rtpmap| rtpmap.encoding_name)
+    }
+
+    /// Resolves the packetization time (in milliseconds) of the given payload type. The
+    /// `mptime` value of the payload type (matched by its position in the media line) takes
+    /// precedence over `ptime`. The result is then raised to the minimum that `maxprate` allows
+    /// and capped by `maxptime`. When the two conflict (`1000 / maxprate` is above `maxptime`),
+    /// `maxptime` wins, since it is the limit the receiver can't handle anything beyond. Returns
+    /// `None` if neither `mptime` nor `ptime` is set.
+    pub fn effective_ptime(&self, payload_type: u8) -> Option<f32> {
+        use lines::Attribute;
+
+        let position = self.media.formats.position(payload_type);
+
+        let mptime = self
+            .attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                Attribute::Mptime(mptime) => {
+                    position.and_then(|p| mptime.get(p).copied().flatten())
+                }
+                _ => None,
+            });
+        let ptime = self
+            .attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                Attribute::Ptime(ptime) => Some(*ptime),
+                _ => None,
+            });
+        let maxptime = self
+            .attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                Attribute::Maxptime(maxptime) => Some(*maxptime),
+                _ => None,
+            });
+        let maxprate = self
+            .attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                Attribute::Maxprate(maxprate) if *maxprate > 0.0 => Some(*maxprate),
+                _ => None,
+            });
+
+        let mut ptime = mptime.or(ptime)?;
+        if let Some(maxprate) = maxprate {
+            ptime = ptime.max(1000.0 / maxprate);
+        }
+        if let Some(maxptime) = maxptime {
+            ptime = ptime.min(maxptime);
+        }
+
+        Some(ptime)
+    }
+
+    /// Combines the media level bandwidth lines with the session level ones (usually
+    /// [crate::SessionDescription::bandwidths]) into a budget in bits per second.
+    ///
+    /// The media bandwidth is taken from `TIAS` or else `AS`, with media level lines overriding
+    /// session level ones (so a media level `AS` wins over a session level `TIAS`), and is capped
+    /// by a session level `CT`. The RTCP share is taken from
+    /// `RS`/`RR` (again media level first) or, when missing, defaults to 1.25% and 3.75% of the
+    /// media bandwidth respectively, as defined in
+    /// [RFC3556](https://www.rfc-editor.org/rfc/rfc3556.html). Returns `None` if no media
+    /// bandwidth can be found.
+    pub fn effective_bandwidth(
+        &self,
+        session_bandwidths: &[lines::Bandwidth],
+    ) -> Option<lines::bandwidth::EffectiveBandwidth> {
+        use lines::bandwidth::{Bwtype, EffectiveBandwidth};
+
+        let find_in = |bandwidths: &[lines::Bandwidth], bwtype: &Bwtype| {
+            bandwidths
+                .iter()
+                .find(|b| &b.bwtype == bwtype)
+                .and_then(|b| b.bps())
+        };
+        let find = |bwtype: Bwtype| {
+            find_in(&self.bandwidths, &bwtype).or_else(|| find_in(session_bandwidths, &bwtype))
+        };
+
+        let mut media = find_in(&self.bandwidths, &Bwtype::Tias)
+            .or_else(|| find_in(&self.bandwidths, &Bwtype::As))
+            .or_else(|| find_in(session_bandwidths, &Bwtype::Tias))
+            .or_else(|| find_in(session_bandwidths, &Bwtype::As))?;
+        if let Some(ct) = session_bandwidths
+            .iter()
+            .find(|b| b.bwtype == Bwtype::Ct)
+            .and_then(|b| b.bps())
+        {
+            media = media.min(ct);
+        }
+
+        Some(EffectiveBandwidth {
+            media,
+            rtcp_senders: find(Bwtype::Rs).unwrap_or(media / 80),
+            rtcp_receivers: find(Bwtype::Rr).unwrap_or(media * 3 / 80),
+        })
+    }
+}
+
 impl<'a> TryFrom<Tokenizer<'a>> for MediaDescription {
     type Error = Error;
 
     fn try_from(tokenizer: Tokenizer<'a>) -> Result<Self, Self::Error> {
         Ok(Self {
-            media: tokenizer.media.try_into()?,
+            media: located(tokenizer.media.media, tokenizer.media)?,
             info: tokenizer.info.map(Into::into),
             connections: tokenizer
                 .connections
                 .into_iter()
-                .map(TryInto::try_into)
+                .map(|connection| located(connection.nettype, connection))
                 .collect::<Result<Vec<_>, _>>()?,
             bandwidths: tokenizer
                 .bandwidths
                 .into_iter()
-                .map(TryInto::try_into)
+                .map(|bandwidth| located(bandwidth.key, bandwidth))
                 .collect::<Result<Vec<_>, _>>()?,
             key: tokenizer.key.map(Into::into),
             attributes: tokenizer
                 .attributes
                 .into_iter()
-                .map(TryInto::try_into)
+                .map(|attribute| located(attribute.key, attribute))
                 .collect::<Result<Vec<_>, _>>()?,
         })
     }
@@ -111,8 +269,8 @@ mod tests {
                     media: lines::media::MediaType::Audio,
                     port: 49170,
                     num_of_ports: None,
-                    proto: lines::media::ProtoType::RtpAvp,
-                    fmt: "0".into()
+                    proto: "RTP/AVP".into(),
+                    formats: lines::media::Formats::PayloadTypes(vec![0])
                 },
                 info: Some(lines::SessionInformation::new("audio media".into())),
                 connections: vec![
@@ -166,8 +324,8 @@ mod tests {
                 media: lines::media::MediaType::Audio,
                 port: 49170,
                 num_of_ports: None,
-                proto: lines::media::ProtoType::RtpAvp,
-                fmt: "0".into(),
+                proto: "RTP/AVP".into(),
+                formats: lines::media::Formats::PayloadTypes(vec![0]),
             },
             info: Some(lines::SessionInformation::new("audio media".into())),
             connections: vec![
@@ -235,8 +393,8 @@ mod tests {
                 media: lines::media::MediaType::Audio,
                 port: 49170,
                 num_of_ports: None,
-                proto: lines::media::ProtoType::RtpAvp,
-                fmt: "0".into(),
+                proto: "RTP/AVP".into(),
+                formats: lines::media::Formats::PayloadTypes(vec![0]),
             },
             info: None,
             connections: vec![],
@@ -245,9 +403,263 @@ mod tests {
             attributes: vec![],
         };
 
+        assert_eq!(media_description.to_string(), "m=audio 49170 RTP/AVP 0");
+    }
+
+    #[test]
+    fn effective_ptime1() {
+        let media_description = MediaDescription {
+            media: lines::Media {
+                media: lines::media::MediaType::Audio,
+                port: 49170,
+                num_of_ports: None,
+                proto: "RTP/AVP".into(),
+                formats: lines::media::Formats::PayloadTypes(vec![0, 8, 101]),
+            },
+            info: None,
+            connections: vec![],
+            bandwidths: vec![],
+            key: None,
+            attributes: vec![
+                lines::Attribute::Ptime(20.0),
+                lines::Attribute::Mptime(vec![Some(30.0), None, Some(50.0)]),
+                lines::Attribute::Maxptime(40.0),
+            ],
+        };
+
+        assert_eq!(media_description.effective_ptime(0), Some(30.0));
+        assert_eq!(media_description.effective_ptime(8), Some(20.0));
+        assert_eq!(media_description.effective_ptime(101), Some(40.0));
+        assert_eq!(media_description.effective_ptime(96), Some(20.0));
+    }
+
+    #[test]
+    fn effective_ptime2() {
+        let media_description = MediaDescription {
+            media: lines::Media {
+                media: lines::media::MediaType::Audio,
+                port: 49170,
+                num_of_ports: None,
+                proto: "RTP/AVP".into(),
+                formats: lines::media::Formats::PayloadTypes(vec![0]),
+            },
+            info: None,
+            connections: vec![],
+            bandwidths: vec![],
+            key: None,
+            attributes: vec![
+                lines::Attribute::Ptime(10.0),
+                lines::Attribute::Maxprate(50.0),
+            ],
+        };
+
+        assert_eq!(media_description.effective_ptime(0), Some(20.0));
+    }
+
+    #[test]
+    fn effective_ptime3() {
+        let media_description = MediaDescription {
+            media: lines::Media {
+                media: lines::media::MediaType::Audio,
+                port: 49170,
+                num_of_ports: None,
+                proto: "RTP/AVP".into(),
+                formats: lines::media::Formats::PayloadTypes(vec![0]),
+            },
+            info: None,
+            connections: vec![],
+            bandwidths: vec![],
+            key: None,
+            attributes: vec![lines::Attribute::Maxptime(40.0)],
+        };
+
+        assert_eq!(media_description.effective_ptime(0), None);
+    }
+
+    #[test]
+    fn effective_ptime4() {
+        let media_description = MediaDescription {
+            media: lines::Media {
+                media: lines::media::MediaType::Audio,
+                port: 49170,
+                num_of_ports: None,
+                proto: "RTP/AVP".into(),
+                formats: lines::media::Formats::PayloadTypes(vec![0]),
+            },
+            info: None,
+            connections: vec![],
+            bandwidths: vec![],
+            key: None,
+            attributes: vec![
+                lines::Attribute::Ptime(20.0),
+                lines::Attribute::Maxptime(40.0),
+                lines::Attribute::Maxprate(10.0),
+            ],
+        };
+
+        assert_eq!(media_description.effective_ptime(0), Some(40.0));
+    }
+
+    #[test]
+    fn codec1() {
+        let media_description = MediaDescription {
+            media: lines::Media {
+                media: lines::media::MediaType::Audio,
+                port: 49170,
+                num_of_ports: None,
+                proto: "RTP/AVP".into(),
+                formats: lines::media::Formats::PayloadTypes(vec![0, 18, 96]),
+            },
+            info: None,
+            connections: vec![],
+            bandwidths: vec![],
+            key: None,
+            attributes: vec![lines::Attribute::Rtpmap(lines::attribute::Rtpmap {
+                payload_type: 96,
+                encoding_name: "opus".into(),
+                clock_rate: 48000,
+                encoding_params: Some(2),
+            })],
+        };
+
+        assert_eq!(
+            media_description.codec(0),
+            Some(lines::attribute::EncodingName::Pcmu)
+        );
+        assert_eq!(
+            media_description.codec(18),
+            Some(lines::attribute::EncodingName::G729)
+        );
+        assert_eq!(
+            media_description.codec(96),
+            Some(lines::attribute::EncodingName::Opus)
+        );
+        assert_eq!(media_description.codec(97), None);
+    }
+
+    #[test]
+    fn remove_payload_type1() {
+        let mut media_description = MediaDescription::try_from(
+            Tokenizer::tokenize(concat!(
+                "m=video 51372 RTP/AVP 96 97\r\n",
+                "a=rtpmap:96 VP8/90000\r\n",
+                "a=rtcp-fb:96 nack\r\n",
+                "a=rtpmap:97 H264/90000\r\n",
+                "a=fmtp:97 profile-level-id=42e01f\r\n",
+                "a=rtcp-fb:97 nack pli\r\n",
+                "a=sendrecv\r\n",
+            ))
+            .unwrap()
+            .1,
+        )
+        .unwrap();
+
+        assert!(media_description.remove_payload_type(97));
+        assert!(!media_description.remove_payload_type(97));
         assert_eq!(
             media_description.to_string(),
-            concat!("m=audio 49170 RTP/AVP 0",)
+            concat!(
+                "m=video 51372 RTP/AVP 96\r\n",
+                "a=rtpmap:96 VP8/90000\r\n",
+                "a=rtcp-fb:96 nack\r\n",
+                "a=sendrecv"
+            )
+        );
+    }
+
+    #[test]
+    fn effective_bandwidth1() {
+        let media_description = MediaDescription::try_from(
+            Tokenizer::tokenize(concat!(
+                "m=video 51372 RTP/AVP 96\r\n",
+                "b=TIAS:2000000\r\n",
+                "b=RR:0\r\n",
+            ))
+            .unwrap()
+            .1,
+        )
+        .unwrap();
+        let session_bandwidths = vec![
+            lines::Bandwidth {
+                bwtype: lines::bandwidth::Bwtype::Ct,
+                bandwidth: 1600,
+            },
+            lines::Bandwidth {
+                bwtype: lines::bandwidth::Bwtype::Rs,
+                bandwidth: 20,
+            },
+        ];
+
+        assert_eq!(
+            media_description.effective_bandwidth(&session_bandwidths),
+            Some(lines::bandwidth::EffectiveBandwidth {
+                media: 1_600_000,
+                rtcp_senders: 20_000,
+                rtcp_receivers: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn effective_bandwidth2() {
+        let media_description = MediaDescription::try_from(
+            Tokenizer::tokenize(concat!("m=audio 49170 RTP/AVP 0\r\n", "b=AS:64\r\n"))
+                .unwrap()
+                .1,
+        )
+        .unwrap();
+
+        let bandwidth = media_description.effective_bandwidth(&[]).unwrap();
+        assert_eq!(bandwidth.media, 64_000);
+        assert_eq!(bandwidth.rtcp(), 3_200);
+        assert_eq!(bandwidth.total(), 67_200);
+        assert_eq!(
+            MediaDescription::try_from(
+                Tokenizer::tokenize("m=audio 49170 RTP/AVP 0\r\n")
+                    .unwrap()
+                    .1
+            )
+            .unwrap()
+            .effective_bandwidth(&[]),
+            None
+        );
+    }
+
+    #[test]
+    fn effective_bandwidth3() {
+        let media_description = MediaDescription::try_from(
+            Tokenizer::tokenize(concat!("m=audio 49170 RTP/AVP 0\r\n", "b=AS:64\r\n"))
+                .unwrap()
+                .1,
+        )
+        .unwrap();
+        let session_bandwidths = vec![
+            lines::Bandwidth {
+                bwtype: lines::bandwidth::Bwtype::Tias,
+                bandwidth: 10_000_000,
+            },
+            lines::Bandwidth {
+                bwtype: lines::bandwidth::Bwtype::As,
+                bandwidth: 20_000,
+            },
+        ];
+
+        assert_eq!(
+            media_description
+                .effective_bandwidth(&session_bandwidths)
+                .map(|bandwidth| bandwidth.media),
+            Some(64_000)
+        );
+        assert_eq!(
+            MediaDescription::try_from(
+                Tokenizer::tokenize("m=audio 49170 RTP/AVP 0\r\n")
+                    .unwrap()
+                    .1
+            )
+            .unwrap()
+            .effective_bandwidth(&session_bandwidths)
+            .map(|bandwidth| bandwidth.media),
+            Some(10_000_000)
         );
     }
 }
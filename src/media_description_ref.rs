@@ -0,0 +1,195 @@
+use crate::{
+    error::located,
+    lines::{self, attribute::AttributeRef},
+    media_description::Tokenizer,
+    Error, ErrorKind, MediaDescription,
+};
+use std::convert::TryFrom;
+
+/// A borrowed Media description. Unlike [MediaDescription], it doesn't allocate anything for its
+/// lines: it keeps the tokens of the SDP message and parses them only when asked for. Use
+/// [MediaDescriptionRef::into_owned] to get the (owned) [MediaDescription].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MediaDescriptionRef<'a> {
+    tokenizer: Tokenizer<'a>,
+}
+
+impl<'a> MediaDescriptionRef<'a> {
+    /// The media type, like `audio` or `video`.
+    pub fn media_type(&self) -> &'a str {
+        self.tokenizer.media.media
+    }
+
+    pub fn port(&self) -> &'a str {
+        self.tokenizer.media.port.port
+    }
+
+    pub fn proto(&self) -> &'a str {
+        self.tokenizer.media.proto
+    }
+
+    /// The formats of the media line, as they appear in it.
+    pub fn formats(&self) -> &'a str {
+        self.tokenizer.media.fmt
+    }
+
+    pub fn media(&self) -> Result<lines::Media, Error> {
+        located(self.tokenizer.media.media, self.tokenizer.media.clone())
+    }
+
+    pub fn info(&self) -> Option<&'a str> {
+        self.tokenizer.info.as_ref().map(|info| info.value)
+    }
+
+    pub fn connections(&self) -> impl Iterator<Item = Result<lines::Connection, Error>> + '_ {
+        self.tokenizer
+            .connections
+            .iter()
+            .map(|connection| located(connection.nettype, connection.clone()))
+    }
+
+    pub fn bandwidths(&self) -> impl Iterator<Item = Result<lines::Bandwidth, Error>> + '_ {
+        self.tokenizer
+            .bandwidths
+            .iter()
+            .map(|bandwidth| located(bandwidth.key, bandwidth.clone()))
+    }
+
+    pub fn key(&self) -> Option<lines::Key> {
+        self.tokenizer.key.clone().map(Into::into)
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = AttributeRef<'a>> + '_ {
+        self.tokenizer.attributes.iter().cloned().map(Into::into)
+    }
+
+    /// The first attribute with the given name, if any.
+    pub fn attribute(&self, name: &str) -> Option<AttributeRef<'a>> {
+        self.attributes().find(|attribute| attribute.name == name)
+    }
+
+    /// The `rtpmap` of the given payload type. Only the matching `rtpmap` attribute is parsed. If
+    /// there is no `rtpmap` attribute for it, the static payload types table is used instead (see
+    /// [MediaDescription::rtpmap]).
+    pub fn rtpmap(&self, payload_type: u8) -> Option<Result<lines::attribute::Rtpmap, Error>> {
+        let payload_type_str = payload_type.to_string();
+
+        self.attributes()
+            .find(|attribute| {
+                attribute.name == "rtpmap"
+                    && attribute.value.and_then(|value| value.split(' ').next())
+                        == Some(payload_type_str.as_str())
+            })
+            .map(|attribute| match attribute.parse()? {
+                lines::Attribute::Rtpmap(rtpmap) => Ok(rtpmap),
+                _ => Err(Error::parser(
+                    ErrorKind::attribute("rtpmap"),
+                    "rtpmap attribute",
+                    attribute,
+                )),
+            })
+            .or_else(|| lines::attribute::Rtpmap::from_static(payload_type).map(Ok))
+    }
+
+    /// The `fmtp` of the given format, if any. Only the matching `fmtp` attribute is parsed.
+    pub fn fmtp(&self, fmt: &str) -> Option<Result<lines::attribute::Fmtp, Error>> {
+        self.attributes()
+            .find(|attribute| {
+                attribute.name == "fmtp"
+                    && attribute.value.and_then(|value| value.split(' ').next()) == Some(fmt)
+            })
+            .map(|attribute| match attribute.parse()? {
+                lines::Attribute::Fmtp(fmtp) => Ok(fmtp),
+                _ => Err(Error::parser(
+                    ErrorKind::attribute("fmtp"),
+                    "fmtp attribute",
+                    attribute,
+                )),
+            })
+    }
+
+    /// The direction of this media description, see [MediaDescription::direction].
+    pub fn direction(
+        &self,
+        session_direction: lines::attribute::Direction,
+    ) -> lines::attribute::Direction {
+        lines::attribute::Direction::find_in_refs(self.attributes()).unwrap_or(session_direction)
+    }
+
+    pub fn into_owned(self) -> Result<MediaDescription, Error> {
+        MediaDescription::try_from(self.tokenizer)
+    }
+
+    pub(crate) fn into_tokenizer(self) -> Tokenizer<'a> {
+        self.tokenizer
+    }
+}
+
+impl<'a> From<Tokenizer<'a>> for MediaDescriptionRef<'a> {
+    fn from(tokenizer: Tokenizer<'a>) -> Self {
+        Self { tokenizer }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokenizer() -> Tokenizer<'static> {
+        Tokenizer {
+            media: crate::tokenizers::media::Tokenizer {
+                media: "audio",
+                port: "49170".into(),
+                proto: "RTP/AVP",
+                fmt: "0 99",
+            },
+            info: None,
+            connections: vec![],
+            bandwidths: vec![],
+            key: None,
+            attributes: vec![
+                ("rtpmap", "99 h263-1998/90000").into(),
+                ("fmtp", "99 foo=bar").into(),
+                ("ptime", "foo").into(),
+                "sendonly".into(),
+            ],
+        }
+    }
+
+    #[test]
+    fn accessors1() {
+        let media_description = MediaDescriptionRef::from(tokenizer());
+
+        assert_eq!(media_description.media_type(), "audio");
+        assert_eq!(media_description.port(), "49170");
+        assert_eq!(
+            media_description
+                .rtpmap(99)
+                .map(|r| r.map(|r| r.clock_rate)),
+            Some(Ok(90000))
+        );
+        assert_eq!(
+            media_description.rtpmap(0).map(|r| r.map(|r| r.clock_rate)),
+            Some(Ok(8000))
+        );
+        assert_eq!(
+            media_description.fmtp("99").map(|f| f.map(|f| f.params)),
+            Some(Ok("foo=bar".into()))
+        );
+        assert_eq!(
+            media_description.direction(Default::default()),
+            lines::attribute::Direction::Sendonly
+        );
+        assert_eq!(
+            media_description.attribute("ptime").map(|a| a.value),
+            Some(Some("foo"))
+        );
+    }
+
+    #[test]
+    fn into_owned1() {
+        let media_description = MediaDescriptionRef::from(tokenizer());
+
+        assert!(media_description.into_owned().is_err());
+    }
+}
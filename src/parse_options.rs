@@ -0,0 +1,385 @@
+/// The options of [crate::SessionDescription::parse_with_options] and
+/// [crate::SessionDescription::from_bytes_with_options].
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ParseOptions {
+    pub strictness: Strictness,
+    /// Collect lines of an unknown type (and lines that are not SDP lines at all) into
+    /// [crate::SessionDescription::unknown_lines], instead of failing.
+    pub collect_unknown_lines: bool,
+    pub limits: Limits,
+}
+
+/// Limits on the size of an SDP message, checked before anything is tokenized, so that messages
+/// from untrusted peers can't cause large allocations. Exceeding any of these results in an
+/// [crate::ErrorKind::LimitExceeded] error. The defaults are meant to be safe for public facing
+/// deployments while accepting any reasonable SDP message (including WebRTC ones), use
+/// [Limits::unlimited] to turn them off.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Limits {
+    /// The maximum size of the whole message, in bytes.
+    pub max_size: usize,
+    /// The maximum length of a single line, in bytes, without the line ending.
+    pub max_line_length: usize,
+    pub max_media_descriptions: usize,
+    /// The maximum number of attributes (`a=`) of the session or of a single media description.
+    pub max_attributes: usize,
+    /// The maximum number of `candidate` attributes of the session or of a single media
+    /// description.
+    pub max_candidates: usize,
+    /// The maximum number of parameters of a single `fmtp` attribute.
+    pub max_fmtp_parameters: usize,
+}
+
+/// A limit of [Limits], as found in [crate::ErrorKind::LimitExceeded].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Limit {
+    Size,
+    LineLength,
+    MediaDescriptions,
+    Attributes,
+    Candidates,
+    FmtpParameters,
+}
+
+/// How strictly an SDP message is parsed.
+///
+/// * `Strict` requires the lines in the exact order of
+///   [RFC8866](https://www.rfc-editor.org/rfc/rfc8866.html) and validates values that are
+///   otherwise accepted as is (like the URIs of the `u=` line and of the `control` and `extmap`
+///   attributes, the addresses of the `e=` lines, the numbers of the `p=` lines, the times of
+///   the `r=` lines or the family of the IP addresses of the `o=` and `c=` lines). This is what
+///   `TryFrom<&str>`, [crate::SessionDescription::from_bytes] and
+///   [crate::PreservedSessionDescription::parse] do as well.
+/// * `Lenient` accepts session level and media level lines in any order, reporting any order
+///   violation as a [Diagnostic] of [Severity::Warning]. Session level lines that show up after
+///   the first media line are moved to the session, while any duplicate of a single occurrence
+///   line is ignored.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Strictness {
+    Lenient,
+    Strict,
+}
+
+impl ParseOptions {
+    pub fn with_limits(self, limits: Limits) -> Self {
+        Self { limits, ..self }
+    }
+
+    pub fn collecting_unknown_lines(self) -> Self {
+        Self {
+            collect_unknown_lines: true,
+            ..self
+        }
+    }
+
+    pub fn lenient() -> Self {
+        Self {
+            strictness: Strictness::Lenient,
+            ..Default::default()
+        }
+    }
+
+    pub fn strict() -> Self {
+        Self {
+            strictness: Strictness::Strict,
+            ..Default::default()
+        }
+    }
+}
+
+impl Limits {
+    pub fn unlimited() -> Self {
+        Self {
+            max_size: usize::MAX,
+            max_line_length: usize::MAX,
+            max_media_descriptions: usize::MAX,
+            max_attributes: usize::MAX,
+            max_candidates: usize::MAX,
+            max_fmtp_parameters: usize::MAX,
+        }
+    }
+
+    /// Checks the given SDP message against the limits, going through it only once without
+    /// allocating anything.
+    pub(crate) fn check(&self, input: &[u8]) -> Result<(), crate::Error> {
+        let exceeded = |limit: Limit, max: usize, line: Option<(usize, &[u8])>| {
+            let mut error = exceeded(limit, max);
+            error.location = line.map(|(index, text)| crate::Location {
+                line: index + 1,
+                column: 1,
+                text: String::from_utf8_lossy(text).into(),
+            });
+            Err(error)
+        };
+
+        self.check_size(input.len())?;
+
+        let mut media_descriptions = 0;
+        let mut attributes = 0;
+        let mut candidates = 0;
+        for (index, (text, _)) in crate::parser_utils::split_lines(input).enumerate() {
+            if text.len() > self.max_line_length {
+                return exceeded(Limit::LineLength, self.max_line_length, Some((index, text)));
+            }
+
+            if text.starts_with(b"m=") {
+                media_descriptions += 1;
+                attributes = 0;
+                candidates = 0;
+                if media_descriptions > self.max_media_descriptions {
+                    return exceeded(
+                        Limit::MediaDescriptions,
+                        self.max_media_descriptions,
+                        Some((index, text)),
+                    );
+                }
+            } else if text.starts_with(b"a=") {
+                let attribute = &text[2..];
+                attributes += 1;
+                if attributes > self.max_attributes {
+                    return exceeded(Limit::Attributes, self.max_attributes, Some((index, text)));
+                }
+                if attribute.starts_with(b"candidate:") {
+                    candidates += 1;
+                    if candidates > self.max_candidates {
+                        return exceeded(
+                            Limit::Candidates,
+                            self.max_candidates,
+                            Some((index, text)),
+                        );
+                    }
+                }
+                if attribute.starts_with(b"fmtp:") {
+                    let fmtp = &attribute[5..];
+                    let parameters =
+                        fmtp.iter()
+                            .position(|byte| *byte == b' ')
+                            .map_or(0, |position| {
+                                fmtp[position..]
+                                    .split(|byte| *byte == b';')
+                                    .filter(|param| {
+                                        param.iter().any(|byte| !byte.is_ascii_whitespace())
+                                    })
+                                    .count()
+                            });
+                    if parameters > self.max_fmtp_parameters {
+                        return exceeded(
+                            Limit::FmtpParameters,
+                            self.max_fmtp_parameters,
+                            Some((index, text)),
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks the size of an SDP message against the limits, before having all of it.
+    pub(crate) fn check_size(&self, size: usize) -> Result<(), crate::Error> {
+        match size > self.max_size {
+            true => Err(exceeded(Limit::Size, self.max_size)),
+            false => Ok(()),
+        }
+    }
+}
+
+fn exceeded(limit: Limit, max: usize) -> crate::Error {
+    crate::Error::new(
+        crate::ErrorKind::LimitExceeded { limit },
+        format!("{} limit of {} exceeded", limit, max),
+    )
+}
+
+impl Default for Strictness {
+    fn default() -> Self {
+        Self::Strict
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_size: 64 * 1024,
+            max_line_length: 4 * 1024,
+            max_media_descriptions: 64,
+            max_attributes: 256,
+            max_candidates: 64,
+            max_fmtp_parameters: 64,
+        }
+    }
+}
+
+impl std::fmt::Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Size => write!(f, "size"),
+            Self::LineLength => write!(f, "line length"),
+            Self::MediaDescriptions => write!(f, "media descriptions"),
+            Self::Attributes => write!(f, "attributes"),
+            Self::Candidates => write!(f, "candidates"),
+            Self::FmtpParameters => write!(f, "fmtp parameters"),
+        }
+    }
+}
+
+/// A problem found while parsing an SDP message, either in [Strictness::Lenient] mode (where
+/// problems are always warnings) or with [crate::SessionDescription::parse_with_diagnostics].
+/// `line` is the (1-based) line number of the SDP message where the problem was found, if it can
+/// be tied to a line, while `error` is the underlying error, if any.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub line: Option<usize>,
+    pub message: String,
+    pub error: Option<crate::Error>,
+}
+
+/// The severity of a [Diagnostic].
+///
+/// * `Error` means that the SDP message is invalid: the offending part was skipped or kept as is.
+/// * `Warning` means that the SDP message violates RFC8866 in a way that could be recovered from
+///   (like lines out of order or lines of an unknown type).
+/// * `Info` points out something that is valid but might not be intended (like attributes that are
+///   not interpreted).
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, line: Option<usize>, message: String) -> Self {
+        Self {
+            severity,
+            line,
+            message,
+            error: None,
+        }
+    }
+
+    /// A warning found at the given line.
+    pub fn warning(line: usize, message: String) -> Self {
+        Self::new(Severity::Warning, Some(line), message)
+    }
+
+    /// An error diagnostic out of the given error, along with what was done to recover from it.
+    pub fn from_error(error: crate::Error, recovery: &str) -> Self {
+        Self {
+            severity: Severity::Error,
+            line: error.line(),
+            message: format!("{}; {}", error.message, recovery),
+            error: Some(error),
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{} at line {}: {}", self.severity, line, self.message),
+            None => write!(f, "{}: {}", self.severity, self.message),
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDP: &str = concat!(
+        "v=0\r\n",
+        "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+        "s=-\r\n",
+        "t=0 0\r\n",
+        "m=audio 49170 RTP/AVP 99\r\n",
+        "a=rtpmap:99 opus/48000/2\r\n",
+        "a=fmtp:99 minptime=10;useinbandfec=1\r\n",
+        "m=video 51372 RTP/AVP 99\r\n",
+    );
+
+    fn exceeded(limits: Limits) -> Option<(Limit, Option<usize>)> {
+        match limits.check(SDP.as_bytes()) {
+            Ok(()) => None,
+            Err(error) => match error.kind {
+                crate::ErrorKind::LimitExceeded { limit } => Some((limit, error.line())),
+                _ => panic!("unexpected error kind"),
+            },
+        }
+    }
+
+    #[test]
+    fn check1() {
+        assert_eq!(exceeded(Limits::default()), None);
+        assert_eq!(exceeded(Limits::unlimited()), None);
+    }
+
+    #[test]
+    fn check2() {
+        let limits = Limits::default();
+
+        assert_eq!(
+            exceeded(Limits {
+                max_size: 100,
+                ..limits
+            })
+            .map(|(limit, _)| limit),
+            Some(Limit::Size)
+        );
+        assert_eq!(
+            exceeded(Limits {
+                max_line_length: 30,
+                ..limits
+            }),
+            Some((Limit::LineLength, Some(2)))
+        );
+        assert_eq!(
+            exceeded(Limits {
+                max_media_descriptions: 1,
+                ..limits
+            }),
+            Some((Limit::MediaDescriptions, Some(8)))
+        );
+        assert_eq!(
+            exceeded(Limits {
+                max_attributes: 1,
+                ..limits
+            }),
+            Some((Limit::Attributes, Some(7)))
+        );
+        assert_eq!(
+            exceeded(Limits {
+                max_fmtp_parameters: 1,
+                ..limits
+            }),
+            Some((Limit::FmtpParameters, Some(7)))
+        );
+    }
+
+    #[test]
+    fn check3() {
+        let sdp = SDP.replace("\r\n", "\r");
+        let limits = Limits {
+            max_line_length: 50,
+            max_media_descriptions: 1,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            limits.check(sdp.as_bytes()).map_err(|error| error.line()),
+            Err(Some(8))
+        );
+    }
+}
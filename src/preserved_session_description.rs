@@ -0,0 +1,271 @@
+use crate::{parser_utils::split_lines, Error, SessionDescription};
+use std::convert::TryFrom;
+
+/// A Session description that remembers the original text of the SDP message it was parsed
+/// from, so that it can be written back byte by byte. Each line keeps its original text and line
+/// ending, and only lines whose typed value has been changed (through
+/// [PreservedSessionDescription::session_description_mut]) are re-rendered. New lines get the line
+/// ending of the line before them. This is useful when the original bytes are needed as long as
+/// nothing changed, like for signatures over SDP bodies.
+///
+/// The `Display` impl writes the preserved representation, while the `Display` impl of the inner
+/// [SessionDescription] always writes its canonical representation.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PreservedSessionDescription {
+    session_description: SessionDescription,
+    //the canonical rendering of each original line, along with its original text
+    lines: Vec<(String, OriginalLine)>,
+    //any blank lines after the last line
+    trailer: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct OriginalLine {
+    text: String,
+    ending: String,
+}
+
+impl PreservedSessionDescription {
+    /// Parses an SDP message like `TryFrom<&str>` for [SessionDescription] does, with the
+    /// default [crate::Limits], see [PreservedSessionDescription::parse_with_limits].
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        Self::parse_with_limits(input, &Default::default())
+    }
+
+    /// Like [PreservedSessionDescription::parse], but checking the input against the given
+    /// [crate::Limits]. Lines are always parsed strictly, so that they can be matched one to one
+    /// with the original lines.
+    pub fn parse_with_limits(input: &str, limits: &crate::Limits) -> Result<Self, Error> {
+        let session_description = SessionDescription::parse_with_limits(input, limits)?;
+
+        let mut original_lines = split_lines(input.as_bytes())
+            .map(|(text, ending)| OriginalLine {
+                //line endings are ASCII, so the line and its ending are valid UTF-8 on their own
+                text: String::from_utf8_lossy(text).into(),
+                ending: String::from_utf8_lossy(ending).into(),
+            })
+            .collect::<Vec<_>>();
+        //trailing blank lines are kept as they are
+        let mut trailer = String::new();
+        while let Some(line) = original_lines.last() {
+            if !line.text.trim().is_empty() {
+                break;
+            }
+            trailer.insert_str(0, &format!("{}{}", line.text, line.ending));
+            original_lines.pop();
+        }
+
+        let canonical_lines = canonical_lines(&session_description);
+        //lines that can't be matched one to one (shouldn't happen) are not preserved, which is
+        //reported by is_preserved
+        let lines = match canonical_lines.len() == original_lines.len() {
+            true => canonical_lines.into_iter().zip(original_lines).collect(),
+            false => vec![],
+        };
+
+        Ok(Self {
+            session_description,
+            lines,
+            trailer,
+        })
+    }
+
+    /// Whether the original text could be matched line by line with the parsed session
+    /// description. If not (which shouldn't happen), the `Display` impl writes the canonical
+    /// representation instead.
+    pub fn is_preserved(&self) -> bool {
+        !self.lines.is_empty()
+    }
+
+    pub fn session_description(&self) -> &SessionDescription {
+        &self.session_description
+    }
+
+    /// The inner session description, for changing it. Changed lines are re-rendered, the rest
+    /// are kept as they were.
+    pub fn session_description_mut(&mut self) -> &mut SessionDescription {
+        &mut self.session_description
+    }
+
+    pub fn into_inner(self) -> SessionDescription {
+        self.session_description
+    }
+
+    /// Whether any line has been changed, added or removed since parsing.
+    pub fn is_modified(&self) -> bool {
+        let canonical_lines = canonical_lines(&self.session_description);
+
+        canonical_lines.len() != self.lines.len()
+            || canonical_lines
+                .iter()
+                .zip(self.lines.iter())
+                .any(|(line, (original, _))| line != original)
+    }
+}
+
+//the lines of the canonical rendering of the session description, without line endings
+fn canonical_lines(session_description: &SessionDescription) -> Vec<String> {
+    session_description
+        .to_string()
+        .split_terminator("\r\n")
+        .map(Into::into)
+        .collect()
+}
+
+impl TryFrom<&str> for PreservedSessionDescription {
+    type Error = Error;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        Self::parse(from)
+    }
+}
+
+impl std::str::FromStr for PreservedSessionDescription {
+    type Err = Error;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        Self::parse(str)
+    }
+}
+
+impl From<PreservedSessionDescription> for SessionDescription {
+    fn from(preserved: PreservedSessionDescription) -> Self {
+        preserved.into_inner()
+    }
+}
+
+impl std::fmt::Display for PreservedSessionDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.lines.is_empty() {
+            return write!(f, "{}", self.session_description);
+        }
+
+        //each line is matched with the next original line of the same canonical rendering, so
+        //that unchanged lines keep their original text even if lines were added or removed
+        let mut next = 0;
+        let mut ending = "\r\n";
+        for line in canonical_lines(&self.session_description) {
+            match self.lines[next..]
+                .iter()
+                .position(|(original, _)| *original == line)
+            {
+                Some(position) => {
+                    let original = &self.lines[next + position].1;
+                    ending = &original.ending;
+                    next += position + 1;
+                    write!(f, "{}{}", original.text, original.ending)?;
+                }
+                None => write!(f, "{}{}", line, ending)?,
+            }
+        }
+
+        write!(f, "{}", self.trailer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lines;
+
+    const SDP: &str = concat!(
+        "v=0\n",
+        "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\n",
+        "s=-\r\n",
+        "t=3034423619 3042462419\n",
+        "r=7d 1h 0 25h\n",
+        "a=recvonly\n",
+        "m=audio 49170 RTP/AVP 0\n",
+        "a=ptime:20.0\n",
+        "m=video 51372 RTP/AVP 99\n",
+        "a=rtpmap:99 h263-1998/90000\n",
+    );
+
+    #[test]
+    fn display1() {
+        let preserved = PreservedSessionDescription::parse(SDP).unwrap();
+
+        assert!(preserved.is_preserved());
+        assert!(!preserved.is_modified());
+        assert_eq!(preserved.to_string(), SDP);
+        assert_ne!(preserved.session_description().to_string(), SDP);
+    }
+
+    #[test]
+    fn display2() {
+        let mut preserved = PreservedSessionDescription::parse(SDP).unwrap();
+
+        preserved
+            .session_description_mut()
+            .set_direction(lines::attribute::Direction::Sendrecv);
+        preserved.session_description_mut().media_descriptions[1]
+            .attributes
+            .push(lines::Attribute::Ptime(30.0));
+
+        assert!(preserved.is_modified());
+        assert_eq!(
+            preserved.to_string(),
+            concat!(
+                "v=0\n",
+                "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\n",
+                "s=-\r\n",
+                "t=3034423619 3042462419\n",
+                "r=7d 1h 0 25h\n",
+                "a=sendrecv\n",
+                "m=audio 49170 RTP/AVP 0\n",
+                "a=ptime:20.0\n",
+                "m=video 51372 RTP/AVP 99\n",
+                "a=rtpmap:99 h263-1998/90000\n",
+                "a=ptime:30\n",
+            )
+        );
+    }
+
+    #[test]
+    fn display3() {
+        let sdp = format!("{}\r\n\r\n", SDP);
+        let mut preserved = PreservedSessionDescription::parse(&sdp).unwrap();
+
+        assert_eq!(preserved.to_string(), sdp);
+
+        preserved.session_description_mut().media_descriptions[1]
+            .attributes
+            .push(lines::Attribute::Ptime(30.0));
+        assert_eq!(
+            preserved.to_string(),
+            format!("{}a=ptime:30\n\r\n\r\n", SDP)
+        );
+    }
+
+    #[test]
+    fn display4() {
+        let sdp = SDP.replace("\r\n", "\n").replace('\n', "\r");
+        let mut preserved = PreservedSessionDescription::parse(&sdp).unwrap();
+
+        assert!(preserved.is_preserved());
+        assert_eq!(preserved.to_string(), sdp);
+
+        preserved.session_description_mut().media_descriptions[1]
+            .attributes
+            .push(lines::Attribute::Ptime(30.0));
+        assert_eq!(preserved.to_string(), format!("{}a=ptime:30\r", sdp));
+    }
+
+    #[test]
+    fn parse1() {
+        let limits = crate::Limits {
+            max_attributes: 0,
+            ..Default::default()
+        };
+
+        let error = PreservedSessionDescription::parse_with_limits(SDP, &limits).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &crate::ErrorKind::LimitExceeded {
+                limit: crate::Limit::Attributes
+            }
+        );
+        assert_eq!(error.line(), Some(6));
+        assert!(PreservedSessionDescription::parse_with_limits(SDP, &Default::default()).is_ok());
+    }
+}
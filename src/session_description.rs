@@ -1,4 +1,9 @@
-use crate::{lines, Error};
+use crate::{
+    error::{located, ErrorKind, Location},
+    lines,
+    parser_utils::split_lines,
+    Error,
+};
 use std::convert::{TryFrom, TryInto};
 use vec1::Vec1;
 
@@ -8,6 +13,11 @@ pub use crate::tokenizers::session_description::Tokenizer;
 
 /// The Session description. This is the main `sdp-rs` type that describes the SDP message.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct SessionDescription {
     pub version: lines::Version,
     pub origin: lines::Origin,
@@ -22,23 +32,781 @@ pub struct SessionDescription {
     pub key: Option<lines::Key>,
     pub attributes: Vec<lines::Attribute>,
     pub media_descriptions: Vec<crate::MediaDescription>,
+    /// The lines with an unknown type that were ignored while parsing, only collected when
+    /// [crate::ParseOptions::collect_unknown_lines] is set. These are not written back when the
+    /// session description is displayed.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub unknown_lines: Vec<String>,
+}
+
+impl SessionDescription {
+    /// The session level direction, as defined by the session level attributes. Defaults to
+    /// `sendrecv` when no direction attribute is present.
+    pub fn direction(&self) -> lines::attribute::Direction {
+        lines::attribute::Direction::find_in(&self.attributes).unwrap_or_default()
+    }
+
+    /// Sets the session level direction, replacing any existing direction attribute.
+    pub fn set_direction(&mut self, direction: lines::attribute::Direction) {
+        direction.replace_in(&mut self.attributes)
+    }
+
+    /// Increments the session version of the origin line, as required whenever the session
+    /// description is modified (like in a re-offer). Fails if the session version can't be
+    /// incremented (see [lines::origin::SessionNumber::increment]).
+    pub fn bump_version(&mut self) -> Result<(), Error> {
+        self.origin.sess_version.increment()
+    }
+
+    /// Whether the session is active at the given instant, according to any of its times (see
+    /// [crate::Time::is_active_at]).
+    pub fn is_active_at(&self, instant: chrono::DateTime<chrono::Utc>) -> bool {
+        self.times.iter().any(|time| time.is_active_at(instant))
+    }
+}
+
+impl SessionDescription {
+    /// Parses an SDP message according to the given [crate::ParseOptions], returning the
+    /// warnings found along with the session description. In strict mode there are no warnings,
+    /// any violation is an error. In any mode, the whole input must be consumed: any line that
+    /// could not be tokenized results in an [ErrorKind::UnexpectedLine] error, unless lines of an
+    /// unknown type are collected instead. The input is checked against the
+    /// [crate::ParseOptions::limits] before anything else.
+    pub fn parse_with_options(
+        input: &str,
+        options: &crate::ParseOptions,
+    ) -> Result<(Self, Vec<crate::Diagnostic>), Error> {
+        options.limits.check(input.as_bytes())?;
+        if !options.collect_unknown_lines {
+            return Self::parse_known_lines(input, options).map_err(|e| e.locate(input));
+        }
+
+        //unknown lines are left out, keeping the original line number of each known line
+        let mut known_lines = String::with_capacity(input.len());
+        let mut line_numbers = vec![];
+        let mut unknown_lines = vec![];
+        for (index, (line, ending)) in split_lines(input.as_bytes()).enumerate() {
+            //line endings are ASCII, so the line and its ending are valid UTF-8 on their own
+            let line = std::str::from_utf8(line).expect("line of a str");
+            let ending = std::str::from_utf8(ending).expect("line ending");
+            match line.as_bytes() {
+                [c, b'=', ..] if b"vosiuepcbtrzkam".contains(c) => {
+                    known_lines.push_str(line);
+                    known_lines.push_str(ending);
+                    line_numbers.push(index + 1);
+                }
+                _ if line.trim().is_empty() => {
+                    known_lines.push_str(line);
+                    known_lines.push_str(ending);
+                }
+                _ => unknown_lines.push(line.into()),
+            }
+        }
+        let original_line = |line: usize| line_numbers.get(line - 1).copied().unwrap_or(line);
+
+        match Self::parse_known_lines(&known_lines, options).map_err(|e| e.locate(&known_lines)) {
+            Ok((mut session_description, warnings)) => {
+                session_description.unknown_lines = unknown_lines;
+                Ok((
+                    session_description,
+                    warnings
+                        .into_iter()
+                        .map(|mut w| {
+                            w.line = w.line.map(original_line);
+                            w
+                        })
+                        .collect(),
+                ))
+            }
+            Err(mut error) => {
+                if let Some(location) = &mut error.location {
+                    location.line = original_line(location.line);
+                }
+                Err(error)
+            }
+        }
+    }
+
+    //converts the tokenizer like `TryFrom` does, additionally validating the values that are
+    //otherwise accepted as is, as strict mode requires
+    fn try_from_strict(tokenizer: Tokenizer<'_>) -> Result<Self, Error> {
+        let uri = tokenizer.uri.as_ref().map(|uri| uri.value);
+        let emails = tokenizer
+            .emails
+            .iter()
+            .map(|email| email.value)
+            .collect::<Vec<_>>();
+        let phones = tokenizer
+            .phones
+            .iter()
+            .map(|phone| phone.value)
+            .collect::<Vec<_>>();
+        let repeats = tokenizer
+            .times
+            .iter()
+            .flat_map(|time| time.repeat.iter().map(|repeat| repeat.interval))
+            .collect::<Vec<_>>();
+        let attributes = std::iter::once(&tokenizer.attributes)
+            .chain(tokenizer.media_descriptions.iter().map(|m| &m.attributes))
+            .flat_map(|attributes| attributes.iter())
+            .map(|attribute| attribute.value.unwrap_or(attribute.key))
+            .collect::<Vec<_>>();
+        let addresses = std::iter::once(tokenizer.origin.unicast_address)
+            .chain(
+                tokenizer
+                    .connection
+                    .iter()
+                    .map(|c| c.connection_address.base),
+            )
+            .chain(
+                tokenizer
+                    .media_descriptions
+                    .iter()
+                    .flat_map(|m| m.connections.iter())
+                    .map(|c| c.connection_address.base),
+            )
+            .collect::<Vec<_>>();
+        let session_description = Self::try_from(tokenizer)?;
+
+        let origin = &session_description.origin;
+        let connections = session_description
+            .connection
+            .iter()
+            .chain(
+                session_description
+                    .media_descriptions
+                    .iter()
+                    .flat_map(|m| m.connections.iter()),
+            )
+            .map(|c| (&c.connection_address.base, &c.addrtype));
+        for ((address, addrtype), token) in
+            std::iter::once((&origin.unicast_address, &origin.addrtype))
+                .chain(connections)
+                .zip(addresses)
+        {
+            address.validate(addrtype).map_err(|e| e.in_token(token))?;
+        }
+        if let (Some(uri), Some(token)) = (&session_description.uri, uri) {
+            uri.validate().map_err(|e| e.in_token(token))?;
+        }
+        for (email, token) in session_description.emails.iter().zip(emails) {
+            email.validate().map_err(|e| e.in_token(token))?;
+        }
+        for (phone, token) in session_description.phones.iter().zip(phones) {
+            phone.validate().map_err(|e| e.in_token(token))?;
+        }
+        let time_repeats = session_description
+            .times
+            .iter()
+            .flat_map(|time| time.repeat.iter());
+        for (repeat, token) in time_repeats.zip(repeats) {
+            repeat.validate().map_err(|e| e.in_token(token))?;
+        }
+        let all_attributes = std::iter::once(&session_description.attributes)
+            .chain(
+                session_description
+                    .media_descriptions
+                    .iter()
+                    .map(|m| &m.attributes),
+            )
+            .flat_map(|attributes| attributes.iter());
+        for (attribute, token) in all_attributes.zip(attributes) {
+            let uri = match attribute {
+                lines::Attribute::Control(uri) => uri,
+                lines::Attribute::Extmap(extmap) => &extmap.uri,
+                _ => continue,
+            };
+            uri.validate().map_err(|e| e.in_token(token))?;
+        }
+
+        Ok(session_description)
+    }
+
+    fn parse_known_lines(
+        input: &str,
+        options: &crate::ParseOptions,
+    ) -> Result<(Self, Vec<crate::Diagnostic>), Error> {
+        match options.strictness {
+            crate::Strictness::Strict => {
+                let (rem, tokenizer) = Tokenizer::tokenize(input)?;
+                ensure_consumed(input, rem)?;
+                Ok((Self::try_from_strict(tokenizer)?, vec![]))
+            }
+            crate::Strictness::Lenient => {
+                let (rem, (tokenizer, warnings)) = Tokenizer::tokenize_lenient(input)?;
+                ensure_consumed(input, rem)?;
+
+                Ok((Self::try_from(tokenizer)?, warnings))
+            }
+        }
+    }
+}
+
+impl SessionDescription {
+    /// Parses an SDP message going past as many problems as possible, reporting each one as a
+    /// [crate::Diagnostic], in line order. Lines that can't be tokenized or are of an unknown type
+    /// are skipped, attributes with an invalid value are kept as [lines::Attribute::Other] and
+    /// invalid media descriptions are skipped altogether. Lines are accepted in any order, like in
+    /// [crate::Strictness::Lenient] mode. No session description is returned only when one of the
+    /// mandatory lines (`v=`, `o=`, `s=`, `t=`) is missing or invalid, or when the input exceeds
+    /// the default [crate::Limits], see [SessionDescription::parse_with_diagnostics_and_limits].
+    pub fn parse_with_diagnostics(input: &str) -> (Option<Self>, Vec<crate::Diagnostic>) {
+        Self::parse_with_diagnostics_and_limits(input, &Default::default())
+    }
+
+    /// Like [SessionDescription::parse_with_diagnostics], but checking the input against the
+    /// given [crate::Limits].
+    pub fn parse_with_diagnostics_and_limits(
+        input: &str,
+        limits: &crate::Limits,
+    ) -> (Option<Self>, Vec<crate::Diagnostic>) {
+        use crate::{Diagnostic, Severity};
+
+        let mut diagnostics = Diagnostics::default();
+        if let Err(error) = limits.check(input.as_bytes()) {
+            diagnostics.list.push(Diagnostic::from_error(
+                error,
+                "can't parse session description",
+            ));
+            return (None, diagnostics.finish());
+        }
+        let mut known_lines = String::with_capacity(input.len());
+        let mut skipping_media = false;
+        for (index, (line, _)) in split_lines(input.as_bytes()).enumerate() {
+            //line endings are ASCII, so the line is valid UTF-8 on its own
+            let line = std::str::from_utf8(line).expect("line of a str");
+            let number = index + 1;
+            let tokenized = || {
+                //line tokenizers expect the line ending
+                let line = format!("{}\r\n", line);
+                crate::tokenizers::session_description::tokenize_line(&line).map_err(|e| {
+                    let mut error = Error::from(e).locate(&line);
+                    if let Some(location) = &mut error.location {
+                        location.line = number;
+                    }
+                    error
+                })
+            };
+
+            match line.as_bytes() {
+                [b'm', b'=', ..] => {
+                    skipping_media = match tokenized() {
+                        Ok(()) => false,
+                        Err(error) => {
+                            diagnostics
+                                .list
+                                .push(Diagnostic::from_error(error, "skipping media description"));
+                            true
+                        }
+                    };
+                    if skipping_media {
+                        continue;
+                    }
+                }
+                _ if skipping_media => continue,
+                _ if line.trim().is_empty() => {
+                    diagnostics.list.push(Diagnostic::new(
+                        Severity::Warning,
+                        Some(number),
+                        "empty line, ignoring".into(),
+                    ));
+                    continue;
+                }
+                [c, b'=', ..] if b"vosiuepcbtrzka".contains(c) => {
+                    if let Err(error) = tokenized() {
+                        diagnostics
+                            .list
+                            .push(Diagnostic::from_error(error, "ignoring line"));
+                        continue;
+                    }
+                    if *c == b'a' {
+                        diagnostics.attribute(number, line);
+                    }
+                }
+                [c, b'=', ..] => {
+                    diagnostics.list.push(Diagnostic::new(
+                        Severity::Warning,
+                        Some(number),
+                        format!("unknown {}= line, ignoring", *c as char),
+                    ));
+                    continue;
+                }
+                _ => {
+                    diagnostics.list.push(Diagnostic::new(
+                        Severity::Error,
+                        Some(number),
+                        format!("not an SDP line, ignoring: {}", line),
+                    ));
+                    continue;
+                }
+            }
+
+            diagnostics.line_starts.push(known_lines.len());
+            diagnostics.line_numbers.push(number);
+            known_lines.push_str(line);
+            known_lines.push_str("\r\n");
+        }
+
+        let tokenizer = match Tokenizer::tokenize_lenient(&known_lines) {
+            Ok((_, (tokenizer, warnings))) => {
+                for mut warning in warnings {
+                    warning.line = warning.line.map(|line| diagnostics.original_line(line));
+                    diagnostics.list.push(warning);
+                }
+                tokenizer
+            }
+            Err(e) => {
+                diagnostics.report(&known_lines, e.into(), "can't parse session description");
+                return (None, diagnostics.finish());
+            }
+        };
+
+        let version = located(tokenizer.version.value, tokenizer.version);
+        let origin = located(tokenizer.origin.username, tokenizer.origin);
+        let times = tokenizer
+            .times
+            .into_iter()
+            .filter_map(|time| {
+                let repeats = time
+                    .repeat
+                    .iter()
+                    .map(|repeat| repeat.interval)
+                    .collect::<Vec<_>>();
+                let time: crate::Time =
+                    diagnostics.keep(&known_lines, time.try_into(), "ignoring time description")?;
+                for (repeat, token) in time.repeat.iter().zip(repeats) {
+                    if let Err(error) = repeat.validate() {
+                        let error = error.in_token(token);
+                        diagnostics.report(&known_lines, error, "keeping repeat time as is");
+                    }
+                }
+
+                Some(time)
+            })
+            .collect::<Vec<crate::Time>>();
+        let (version, origin, times) = match (version, origin, Vec1::try_from_vec(times)) {
+            (Ok(version), Ok(origin), Ok(times)) => (version, origin, times),
+            (version, origin, _) => {
+                for error in version.err().into_iter().chain(origin.err()) {
+                    diagnostics.report(&known_lines, error, "can't parse session description");
+                }
+                return (None, diagnostics.finish());
+            }
+        };
+
+        let session_description = Self {
+            version,
+            origin,
+            session_name: tokenizer.session_name.into(),
+            session_info: tokenizer.session_info.map(Into::into),
+            uri: tokenizer.uri.map(Into::into),
+            emails: tokenizer
+                .emails
+                .into_iter()
+                .filter_map(|email| {
+                    let email = located(email.value, email);
+                    diagnostics.keep(&known_lines, email, "ignoring line")
+                })
+                .collect(),
+            phones: tokenizer
+                .phones
+                .into_iter()
+                .filter_map(|phone| {
+                    let phone = located(phone.value, phone);
+                    diagnostics.keep(&known_lines, phone, "ignoring line")
+                })
+                .collect(),
+            connection: tokenizer.connection.and_then(|connection| {
+                let connection = located(connection.nettype, connection);
+                diagnostics.keep(&known_lines, connection, "ignoring line")
+            }),
+            bandwidths: tokenizer
+                .bandwidths
+                .into_iter()
+                .filter_map(|bandwidth| {
+                    let bandwidth = located(bandwidth.key, bandwidth);
+                    diagnostics.keep(&known_lines, bandwidth, "ignoring line")
+                })
+                .collect(),
+            times,
+            key: tokenizer.key.map(Into::into),
+            attributes: tokenizer
+                .attributes
+                .into_iter()
+                .map(attribute_or_other)
+                .collect(),
+            media_descriptions: tokenizer
+                .media_descriptions
+                .into_iter()
+                .filter_map(|mut media_description| {
+                    let attributes = std::mem::take(&mut media_description.attributes);
+                    let media_description =
+                        located(media_description.media.media, media_description);
+                    let mut media_description: crate::MediaDescription = diagnostics.keep(
+                        &known_lines,
+                        media_description,
+                        "skipping media description",
+                    )?;
+                    media_description.attributes =
+                        attributes.into_iter().map(attribute_or_other).collect();
+
+                    Some(media_description)
+                })
+                .collect(),
+            unknown_lines: vec![],
+        };
+
+        (Some(session_description), diagnostics.finish())
+    }
+}
+
+impl SessionDescription {
+    /// Parses an SDP message out of raw bytes (like the body of a SIP message). The free text
+    /// fields (`s=` and `i=`) are decoded using the charset declared by the `a=charset` attribute
+    /// (the media level one for the `i=` line of a media description, falling back to the session
+    /// level one), or as UTF-8 if there is none. If the decoded value differs from the UTF-8
+    /// reading of the bytes, the raw bytes are kept (see [lines::SessionName::raw]). Structural
+    /// lines must be ASCII, while the rest (like `e=` or `a=`) must be UTF-8. Like
+    /// `TryFrom<&str>`, the default [crate::Limits] apply, use
+    /// [SessionDescription::from_bytes_with_options] for anything else.
+    pub fn from_bytes(input: &[u8]) -> Result<Self, Error> {
+        Self::from_bytes_with_limits(input, &Default::default())
+    }
+
+    pub(crate) fn from_bytes_with_limits(
+        input: &[u8],
+        limits: &crate::Limits,
+    ) -> Result<Self, Error> {
+        Self::from_bytes_with(input, limits, |text| {
+            Ok((Self::parse_with_limits(text, limits)?, vec![]))
+        })
+        .map(|(session_description, _)| session_description)
+    }
+
+    /// Like [SessionDescription::from_bytes], but parsing the decoded message according to the
+    /// given [crate::ParseOptions] (see [SessionDescription::parse_with_options]). The raw input
+    /// is checked against the [crate::ParseOptions::limits] before it's decoded.
+    pub fn from_bytes_with_options(
+        input: &[u8],
+        options: &crate::ParseOptions,
+    ) -> Result<(Self, Vec<crate::Diagnostic>), Error> {
+        Self::from_bytes_with(input, &options.limits, |text| {
+            Self::parse_with_options(text, options)
+        })
+    }
+
+    //decodes the input into text, parses it with the given function and then sets the free text
+    //fields that need to keep their raw bytes
+    fn from_bytes_with(
+        input: &[u8],
+        limits: &crate::Limits,
+        parse: impl FnOnce(&str) -> Result<(Self, Vec<crate::Diagnostic>), Error>,
+    ) -> Result<(Self, Vec<crate::Diagnostic>), Error> {
+        limits.check(input)?;
+        let charsets = charsets(input);
+        let mut text = String::with_capacity(input.len());
+        let mut free_texts = vec![];
+        let mut section = 0;
+        for (index, (content, ending)) in split_lines(input).enumerate() {
+            let invalid = |message: String, column: usize| {
+                let mut error = Error::new(ErrorKind::InvalidLine, message);
+                error.location = Some(Location {
+                    line: index + 1,
+                    column: column + 1,
+                    text: String::from_utf8_lossy(content).into(),
+                });
+                error
+            };
+
+            match content {
+                [prefix @ b's', b'=', value @ ..] | [prefix @ b'i', b'=', value @ ..] => {
+                    let decoded = lines::common::decode_text(value, charsets[section]);
+                    if decoded.as_bytes() != value {
+                        free_texts.push((section, *prefix, value));
+                    }
+                    text.push(char::from(*prefix));
+                    text.push('=');
+                    text.push_str(&decoded);
+                }
+                [prefix, b'=', ..] if b"vocbtrzkm".contains(prefix) => {
+                    if let Some(column) = content.iter().position(|byte| !byte.is_ascii()) {
+                        return Err(invalid(
+                            format!("non-ASCII character in {}= line", char::from(content[0])),
+                            column,
+                        ));
+                    }
+                    if content.starts_with(b"m=") {
+                        section += 1;
+                    }
+                    text.push_str(std::str::from_utf8(content).expect("ASCII"));
+                }
+                _ => match std::str::from_utf8(content) {
+                    Ok(content) => text.push_str(content),
+                    Err(e) => {
+                        let column = String::from_utf8_lossy(&content[..e.valid_up_to()])
+                            .chars()
+                            .count();
+                        return Err(invalid("invalid UTF-8 in line".into(), column));
+                    }
+                },
+            }
+            text.push_str(std::str::from_utf8(ending).expect("line ending"));
+        }
+
+        let (mut session_description, diagnostics) = parse(&text)?;
+        for (section, prefix, raw) in free_texts {
+            match (section, prefix) {
+                (0, b's') => {
+                    session_description.session_name =
+                        lines::SessionName::from_bytes(raw, charsets[0])
+                }
+                (0, _) => {
+                    session_description.session_info =
+                        Some(lines::SessionInformation::from_bytes(raw, charsets[0]))
+                }
+                (section, _) => {
+                    if let Some(media_description) =
+                        session_description.media_descriptions.get_mut(section - 1)
+                    {
+                        media_description.info = Some(lines::SessionInformation::from_bytes(
+                            raw,
+                            charsets[section],
+                        ))
+                    }
+                }
+            }
+        }
+
+        Ok((session_description, diagnostics))
+    }
+}
+
+impl SessionDescription {
+    /// Parses an SDP message out of a buffer that might not hold the whole message yet, like when
+    /// reading from a stream. Unlike [SessionDescription::from_bytes], a truncated buffer (one
+    /// that ends in the middle of a line or before all mandatory lines have shown up) results in
+    /// an [ErrorKind::Incomplete] error, with the minimum number of bytes needed when that's known,
+    /// instead of any other error. Since SDP has no length or terminator of its own, a buffer
+    /// that holds a complete line at its end (ending in `\r\n`, `\r` or `\n`) is considered a
+    /// complete message: framing is up to the protocol that carries it. A buffer ending in `\r`
+    /// is considered truncated if its earlier lines end in `\r\n`.
+    pub fn parse_incremental(input: &[u8]) -> Result<Self, Error> {
+        Self::parse_incremental_with_limits(input, &Default::default())
+    }
+
+    /// Like [SessionDescription::parse_incremental], but checking the input against the given
+    /// [crate::Limits].
+    pub fn parse_incremental_with_limits(
+        input: &[u8],
+        limits: &crate::Limits,
+    ) -> Result<Self, Error> {
+        if input.is_empty() {
+            //the shortest possible line is one like `v=\n`
+            return Err(Error::incomplete(Some(3)));
+        }
+        //a trailing `\r` ends the line, unless the lines so far end in `\r\n`, in which case the
+        //`\n` is probably yet to come
+        let crlf = input.windows(2).any(|window| window == b"\r\n");
+        match input.last() {
+            Some(b'\n') => (),
+            Some(b'\r') if !crlf => (),
+            _ => return Err(Error::incomplete(Some(1))),
+        }
+
+        let lines = split_lines(input).count();
+        Self::from_bytes_with_limits(input, limits).map_err(|error| {
+            match (&error.kind, error.line()) {
+                //missing lines at the end of the input are yet to come
+                (ErrorKind::MissingLine, Some(line)) if line == lines + 1 => {
+                    Error::incomplete(None)
+                }
+                _ => error,
+            }
+        })
+    }
+}
+
+//the charset declared by the charset attribute (`a=charset`) of each section, if any: the session
+//first and then each media description, which falls back to the session one
+fn charsets(input: &[u8]) -> Vec<Option<&str>> {
+    let mut charsets: Vec<Option<&str>> = vec![None];
+    for (line, _) in split_lines(input) {
+        if line.starts_with(b"m=") {
+            charsets.push(None);
+        } else if let Some(charset) = Some(line)
+            .filter(|line| line.starts_with(b"a=charset:"))
+            .and_then(|line| std::str::from_utf8(&line[b"a=charset:".len()..]).ok())
+        {
+            let last = charsets.len() - 1;
+            charsets[last].get_or_insert(charset.trim());
+        }
+    }
+    let session = charsets[0];
+
+    charsets
+        .into_iter()
+        .enumerate()
+        .map(|(section, charset)| match section {
+            0 => charset,
+            _ => charset.or(session),
+        })
+        .collect()
+}
+
+//the diagnostics found so far, along with the original line number and the offset (in the
+//lines that are parsed) of each line that is parsed
+#[derive(Default)]
+struct Diagnostics {
+    list: Vec<crate::Diagnostic>,
+    line_numbers: Vec<usize>,
+    line_starts: Vec<usize>,
+}
+
+impl Diagnostics {
+    //attributes are checked while scanning the lines, hence before anything else, so the
+    //diagnostics are put in line order
+    fn finish(mut self) -> Vec<crate::Diagnostic> {
+        self.list.sort_by_key(|diagnostic| diagnostic.line);
+        self.list
+    }
+
+    fn original_line(&self, line: usize) -> usize {
+        self.line_numbers.get(line - 1).copied().unwrap_or(line)
+    }
+
+    //locates the error in the line it points to, which is found by its offset
+    fn report(&mut self, known_lines: &str, mut error: Error, recovery: &str) {
+        if let (None, Some(offset)) = (&error.location, error.offset_in(known_lines)) {
+            let index = self
+                .line_starts
+                .iter()
+                .take_while(|start| **start <= offset)
+                .count()
+                .saturating_sub(1);
+            let start = self.line_starts.get(index).copied().unwrap_or_default();
+            let end = self
+                .line_starts
+                .get(index + 1)
+                .copied()
+                .unwrap_or(known_lines.len());
+            let mut location = Location::at(&known_lines[start..end], offset - start);
+            location.line = self.original_line(index + 1);
+            error.location = Some(location);
+        }
+
+        self.list
+            .push(crate::Diagnostic::from_error(error, recovery));
+    }
+
+    fn keep<T>(
+        &mut self,
+        known_lines: &str,
+        result: Result<T, Error>,
+        recovery: &str,
+    ) -> Option<T> {
+        result
+            .map_err(|error| self.report(known_lines, error, recovery))
+            .ok()
+    }
+
+    //attributes with an invalid value are kept as is, attributes that are not interpreted are
+    //pointed out
+    fn attribute(&mut self, number: usize, line: &str) {
+        //line tokenizers expect the line ending
+        let line = format!("{}\r\n", line);
+        let tokenizer = match crate::tokenizers::key_optvalue::Tokenizer::<'a'>::tokenize(&line) {
+            Ok((_, tokenizer)) => tokenizer,
+            Err(_) => return,
+        };
+
+        let key = tokenizer.key;
+        match located::<_, lines::Attribute>(key, tokenizer) {
+            Ok(lines::Attribute::Other(..)) => self.list.push(crate::Diagnostic::new(
+                crate::Severity::Info,
+                Some(number),
+                format!("attribute a={} is not interpreted, keeping as is", key),
+            )),
+            Ok(_) => (),
+            Err(error) => {
+                let mut error = error.locate(&line);
+                if let Some(location) = &mut error.location {
+                    location.line = number;
+                }
+                self.list.push(crate::Diagnostic::from_error(
+                    error,
+                    "keeping attribute as is",
+                ));
+            }
+        }
+    }
+}
+
+//converts the attribute, keeping it as is when its value is invalid
+fn attribute_or_other(
+    tokenizer: crate::tokenizers::key_optvalue::Tokenizer<'_, 'a'>,
+) -> lines::Attribute {
+    let (key, value) = (tokenizer.key, tokenizer.value);
+
+    lines::Attribute::try_from(tokenizer)
+        .unwrap_or_else(|_| lines::Attribute::Other(key.into(), value.map(Into::into)))
+}
+
+//fails with the first unconsumed line, if there is anything but whitespace left
+pub(crate) fn ensure_consumed(input: &str, rem: &str) -> Result<(), Error> {
+    match rem.trim().is_empty() {
+        true => Ok(()),
+        false => {
+            let offset = input.len() - rem.len() + (rem.len() - rem.trim_start().len());
+            let mut error = Error::new(
+                ErrorKind::UnexpectedLine,
+                format!(
+                    "unexpected line: {}",
+                    rem.trim_start()
+                        .split(['\r', '\n'])
+                        .next()
+                        .unwrap_or_default()
+                ),
+            );
+            error.location = Some(Location::at(input, offset));
+
+            Err(error)
+        }
+    }
 }
 
 impl TryFrom<String> for SessionDescription {
     type Error = Error;
 
     fn try_from(from: String) -> Result<Self, Self::Error> {
-        let tokenizer = Tokenizer::tokenize(&from)?;
-        Self::try_from(tokenizer.1)
+        Self::try_from(from.as_str())
     }
 }
 
+/// Parses an SDP message in strict mode (validating the values that are otherwise accepted as is,
+/// see [crate::Strictness]), with the default [crate::Limits]. Use
+/// [SessionDescription::parse_with_options] for anything else.
 impl TryFrom<&str> for SessionDescription {
     type Error = Error;
 
     fn try_from(from: &str) -> Result<Self, Self::Error> {
-        let tokenizer = Tokenizer::tokenize(from)?;
-        Self::try_from(tokenizer.1)
+        Self::parse_with_limits(from, &Default::default())
+    }
+}
+
+impl SessionDescription {
+    //what `TryFrom<&str>` does, with the given limits
+    pub(crate) fn parse_with_limits(input: &str, limits: &crate::Limits) -> Result<Self, Error> {
+        let parse = || {
+            limits.check(input.as_bytes())?;
+            let (rem, tokenizer) = Tokenizer::tokenize(input)?;
+            ensure_consumed(input, rem)?;
+            Self::try_from_strict(tokenizer)
+        };
+
+        parse().map_err(|error: Error| error.locate(input))
     }
 }
 
@@ -54,26 +822,29 @@ impl<'a> TryFrom<Tokenizer<'a>> for SessionDescription {
 
     fn try_from(tokenizer: Tokenizer<'a>) -> Result<Self, Self::Error> {
         Ok(Self {
-            version: tokenizer.version.try_into()?,
-            origin: tokenizer.origin.try_into()?,
+            version: located(tokenizer.version.value, tokenizer.version)?,
+            origin: located(tokenizer.origin.username, tokenizer.origin)?,
             session_name: tokenizer.session_name.into(),
             session_info: tokenizer.session_info.map(Into::into),
             uri: tokenizer.uri.map(Into::into),
             emails: tokenizer
                 .emails
                 .into_iter()
-                .map(Into::into)
-                .collect::<Vec<_>>(),
+                .map(|email| located(email.value, email))
+                .collect::<Result<Vec<_>, _>>()?,
             phones: tokenizer
                 .phones
                 .into_iter()
-                .map(Into::into)
-                .collect::<Vec<_>>(),
-            connection: tokenizer.connection.map(TryInto::try_into).transpose()?,
+                .map(|phone| located(phone.value, phone))
+                .collect::<Result<Vec<_>, _>>()?,
+            connection: tokenizer
+                .connection
+                .map(|connection| located(connection.nettype, connection))
+                .transpose()?,
             bandwidths: tokenizer
                 .bandwidths
                 .into_iter()
-                .map(TryInto::try_into)
+                .map(|bandwidth| located(bandwidth.key, bandwidth))
                 .collect::<Result<Vec<_>, _>>()?,
             times: tokenizer
                 .times
@@ -81,18 +852,21 @@ impl<'a> TryFrom<Tokenizer<'a>> for SessionDescription {
                 .map(TryInto::try_into)
                 .collect::<Result<Vec<_>, _>>()?
                 .try_into()
-                .map_err(|_| Error::parser("times", "missing time(s) line(s)"))?,
+                .map_err(|_| {
+                    Error::parser(ErrorKind::MissingLine, "times", "missing time(s) line(s)")
+                })?,
             key: tokenizer.key.map(Into::into),
             attributes: tokenizer
                 .attributes
                 .into_iter()
-                .map(TryInto::try_into)
+                .map(|attribute| located(attribute.key, attribute))
                 .collect::<Result<Vec<_>, _>>()?,
             media_descriptions: tokenizer
                 .media_descriptions
                 .into_iter()
                 .map(TryInto::try_into)
                 .collect::<Result<Vec<_>, _>>()?,
+            unknown_lines: vec![],
         })
     }
 }
@@ -235,8 +1009,8 @@ mod tests {
                 media: lines::media::MediaType::Audio,
                 port: 49170,
                 num_of_ports: None,
-                proto: lines::media::ProtoType::RtpAvp,
-                fmt: "0".into(),
+                proto: "RTP/AVP".into(),
+                formats: lines::media::Formats::PayloadTypes(vec![0]),
             },
             info: Some(lines::SessionInformation::new("audio media".into())),
             connections: vec![
@@ -291,7 +1065,7 @@ mod tests {
                     sess_version: "2890842807".into(),
                     nettype: lines::common::Nettype::In,
                     addrtype: lines::common::Addrtype::Ip4,
-                    unicast_address: IpAddr::V4(Ipv4Addr::new(10, 47, 16, 5)),
+                    unicast_address: IpAddr::V4(Ipv4Addr::new(10, 47, 16, 5)).into(),
                 },
                 session_name: lines::SessionName::new("-".into()),
                 session_info: Some(lines::SessionInformation::new(
@@ -300,13 +1074,19 @@ mod tests {
                 uri: Some(lines::Uri::new(
                     "http://www.example.com/seminars/sdp.pdf".into()
                 )),
-                emails: vec![lines::Email::new("alice@example.com (Alice Smith)".into())],
+                emails: vec![lines::Email::with_display_name(
+                    "alice@example.com".into(),
+                    lines::common::DisplayName::new(
+                        "Alice Smith".into(),
+                        lines::common::DisplayNameSyntax::Comment
+                    )
+                )],
                 phones: vec![lines::Phone::new("+1 911-345-1160".into())],
                 connection: Some(lines::Connection {
                     nettype: lines::common::Nettype::In,
                     addrtype: lines::common::Addrtype::Ip4,
                     connection_address: lines::connection::ConnectionAddress {
-                        base: "10.47.16.5".parse().unwrap(),
+                        base: "10.47.16.5".parse::<IpAddr>().unwrap().into(),
                         ttl: None,
                         numaddr: None
                     }
@@ -320,8 +1100,11 @@ mod tests {
                     method: lines::key::KeyMethod::Clear,
                     encryption_key: "password".into()
                 }),
-                attributes: vec![lines::Attribute::Recvonly],
-                media_descriptions: vec![expected_media_description]
+                attributes: vec![lines::Attribute::Direction(
+                    lines::attribute::Direction::Recvonly
+                )],
+                media_descriptions: vec![expected_media_description],
+                unknown_lines: vec![]
             })
         );
     }
@@ -385,8 +1168,8 @@ mod tests {
                 media: lines::media::MediaType::Audio,
                 port: 49170,
                 num_of_ports: None,
-                proto: lines::media::ProtoType::RtpAvp,
-                fmt: "0".into(),
+                proto: "RTP/AVP".into(),
+                formats: lines::media::Formats::PayloadTypes(vec![0]),
             },
             info: None,
             connections: vec![lines::Connection {
@@ -412,7 +1195,7 @@ mod tests {
                     sess_version: "2890842807".into(),
                     nettype: lines::common::Nettype::In,
                     addrtype: lines::common::Addrtype::Ip4,
-                    unicast_address: IpAddr::V4(Ipv4Addr::new(10, 47, 16, 5)),
+                    unicast_address: IpAddr::V4(Ipv4Addr::new(10, 47, 16, 5)).into(),
                 },
                 session_name: lines::SessionName::new("-".into()),
                 session_info: None,
@@ -424,7 +1207,8 @@ mod tests {
                 times: expected_times,
                 key: None,
                 attributes: vec![],
-                media_descriptions: vec![expected_media_description]
+                media_descriptions: vec![expected_media_description],
+                unknown_lines: vec![]
             })
         );
     }
@@ -477,7 +1261,7 @@ mod tests {
                     sess_version: "2890842807".into(),
                     nettype: lines::common::Nettype::In,
                     addrtype: lines::common::Addrtype::Ip4,
-                    unicast_address: IpAddr::V4(Ipv4Addr::new(10, 47, 16, 5)),
+                    unicast_address: IpAddr::V4(Ipv4Addr::new(10, 47, 16, 5)).into(),
                 },
                 session_name: lines::SessionName::new("-".into()),
                 session_info: None,
@@ -489,7 +1273,8 @@ mod tests {
                 times: expected_times,
                 key: None,
                 attributes: vec![],
-                media_descriptions: vec![]
+                media_descriptions: vec![],
+                unknown_lines: vec![]
             })
         );
     }
@@ -586,4 +1371,826 @@ mod tests {
         let parsed_sdp = SessionDescription::try_from(sdp);
         assert!(parsed_sdp.is_err());
     }
+
+    #[test]
+    fn direction1() {
+        use lines::attribute::Direction;
+
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=2854678930 2854679000\r\n",
+            "a=sendonly\r\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+            "m=video 51372 RTP/AVP 99\r\n",
+            "a=inactive\r\n",
+        );
+
+        let parsed_sdp = SessionDescription::try_from(sdp).unwrap();
+        assert_eq!(parsed_sdp.direction(), Direction::Sendonly);
+        assert_eq!(
+            parsed_sdp.media_descriptions[0].direction(parsed_sdp.direction()),
+            Direction::Sendonly
+        );
+        assert_eq!(
+            parsed_sdp.media_descriptions[1].direction(parsed_sdp.direction()),
+            Direction::Inactive
+        );
+    }
+
+    #[test]
+    fn set_direction1() {
+        use lines::attribute::Direction;
+
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=2854678930 2854679000\r\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+            "a=sendrecv\r\n",
+            "a=ptime:20\r\n",
+            "a=recvonly\r\n",
+        );
+
+        let mut parsed_sdp = SessionDescription::try_from(sdp).unwrap();
+        assert_eq!(parsed_sdp.direction(), Direction::Sendrecv);
+
+        parsed_sdp.set_direction(Direction::Inactive);
+        parsed_sdp.media_descriptions[0].set_direction(Direction::Sendonly);
+        assert_eq!(
+            parsed_sdp.to_string(),
+            concat!(
+                "v=0\r\n",
+                "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+                "s=-\r\n",
+                "t=2854678930 2854679000\r\n",
+                "a=inactive\r\n",
+                "m=audio 49170 RTP/AVP 0\r\n",
+                "a=sendonly\r\n",
+                "a=ptime:20\r\n",
+            )
+        );
+    }
+
+    #[test]
+    fn is_active_at1() {
+        use chrono::TimeZone;
+
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=3724394400 0\r\n",
+            "r=1d 1h 0\r\n",
+        );
+
+        let parsed_sdp = SessionDescription::try_from(sdp).unwrap();
+        assert!(parsed_sdp.is_active_at(
+            chrono::Utc
+                .with_ymd_and_hms(2018, 1, 10, 10, 30, 0)
+                .unwrap()
+        ));
+        assert!(!parsed_sdp.is_active_at(
+            chrono::Utc
+                .with_ymd_and_hms(2018, 1, 10, 11, 30, 0)
+                .unwrap()
+        ));
+        assert!(
+            !parsed_sdp.is_active_at(chrono::Utc.with_ymd_and_hms(2018, 1, 7, 10, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn bump_version1() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+        );
+
+        let mut parsed_sdp = SessionDescription::try_from(sdp).unwrap();
+        let origin = parsed_sdp.origin.clone();
+        parsed_sdp.bump_version().unwrap();
+
+        assert_eq!(
+            parsed_sdp.origin.to_string(),
+            "o=Alice 2890844526 2890842808 IN IP4 10.47.16.5"
+        );
+        assert!(parsed_sdp.origin.is_same_session(&origin));
+    }
+
+    #[test]
+    fn parse_with_options1() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "b=AS:128\r\n",
+            "c=IN IP4 10.47.16.5\r\n",
+            "a=recvonly\r\n",
+            "t=0 0\r\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+            "a=rtpmap:0 PCMU/8000\r\n",
+            "c=IN IP4 10.47.16.6\r\n",
+        );
+        let ordered_sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "c=IN IP4 10.47.16.5\r\n",
+            "b=AS:128\r\n",
+            "t=0 0\r\n",
+            "a=recvonly\r\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+            "c=IN IP4 10.47.16.6\r\n",
+            "a=rtpmap:0 PCMU/8000\r\n",
+        );
+
+        assert!(
+            SessionDescription::parse_with_options(sdp, &crate::ParseOptions::strict()).is_err()
+        );
+
+        let (parsed_sdp, warnings) =
+            SessionDescription::parse_with_options(sdp, &crate::ParseOptions::lenient()).unwrap();
+        assert_eq!(
+            parsed_sdp,
+            SessionDescription::try_from(ordered_sdp).unwrap()
+        );
+        assert_eq!(
+            warnings,
+            vec![
+                crate::Diagnostic::warning(5, "c= line out of order".into()),
+                crate::Diagnostic::warning(7, "t= line out of order".into()),
+                crate::Diagnostic::warning(10, "c= line out of order".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_with_options2() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "u=not a uri\r\n",
+            "t=0 0\r\n",
+        );
+
+        assert!(
+            SessionDescription::parse_with_options(sdp, &crate::ParseOptions::strict()).is_err()
+        );
+        assert!(
+            SessionDescription::parse_with_options(sdp, &crate::ParseOptions::lenient()).is_ok()
+        );
+    }
+
+    #[test]
+    fn parse_with_options3() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "p=(555) 123 4567\r\n",
+            "t=0 0\r\n",
+        );
+
+        let error = SessionDescription::parse_with_options(sdp, &crate::ParseOptions::strict())
+            .unwrap_err();
+        assert_eq!(error.line(), Some(4));
+
+        let (parsed_sdp, _) =
+            SessionDescription::parse_with_options(sdp, &crate::ParseOptions::lenient()).unwrap();
+        assert_eq!(
+            parsed_sdp.phones,
+            vec![lines::Phone::new("(555) 123 4567".into())]
+        );
+        assert_eq!(parsed_sdp.to_string(), sdp);
+    }
+
+    #[test]
+    fn fmtp_without_params1() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+            "m=audio 49170 RTP/AVP 0 101\r\n",
+            "a=rtpmap:101 telephone-event/8000\r\n",
+            "a=fmtp:101\r\n",
+        );
+
+        let session_description = SessionDescription::try_from(sdp).unwrap();
+        assert_eq!(session_description.to_string(), sdp);
+    }
+
+    #[test]
+    fn unexpected_line1() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+            "x=unknown\r\n",
+            "m=video 51372 RTP/AVP 99\r\n",
+        );
+
+        let error = SessionDescription::try_from(sdp).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::UnexpectedLine);
+        assert_eq!(error.line(), Some(6));
+        assert_eq!(error.text(), Some("x=unknown"));
+
+        let error = SessionDescription::parse_with_options(sdp, &crate::ParseOptions::lenient())
+            .unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::UnexpectedLine);
+        assert_eq!(error.line(), Some(6));
+        assert_eq!(error.text(), Some("x=unknown"));
+    }
+
+    #[test]
+    fn unexpected_line2() {
+        let sdp = concat!(
+            "v=0\r",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r",
+            "s=-\r",
+            "t=0 0\r",
+            "x=unknown\r",
+            "m=video 51372 RTP/AVP 99\r",
+        );
+
+        let error = SessionDescription::try_from(sdp).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::UnexpectedLine);
+        assert_eq!(error.message, "unexpected line: x=unknown");
+        assert_eq!(error.line(), Some(5));
+        assert_eq!(error.text(), Some("x=unknown"));
+    }
+
+    #[test]
+    fn error_location1() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+            "m=audio 4917o RTP/AVP 0\r\n",
+        );
+
+        let error = SessionDescription::try_from(sdp).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidNumber);
+        assert_eq!(
+            error.location,
+            Some(Location {
+                line: 5,
+                column: 3,
+                text: "m=audio 4917o RTP/AVP 0".into()
+            })
+        );
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn error_location2() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+            "a=rtpmap:foo\r\n",
+        );
+
+        let error = SessionDescription::try_from(sdp).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &ErrorKind::InvalidAttribute {
+                name: "rtpmap".into()
+            }
+        );
+        assert_eq!(error.line(), Some(5));
+        assert_eq!(error.text(), Some("a=rtpmap:foo"));
+    }
+
+    #[test]
+    fn error_location3() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+        );
+
+        let error = SessionDescription::try_from(sdp).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::MissingLine);
+        assert_eq!(error.line(), Some(4));
+    }
+
+    #[test]
+    fn parse_with_options4() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=3034423619 3042462419\r\n",
+            "r=1h 2h 0\r\n",
+        );
+
+        let error = SessionDescription::parse_with_options(sdp, &crate::ParseOptions::strict())
+            .unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidTime);
+        assert_eq!(error.line(), Some(5));
+
+        assert!(
+            SessionDescription::parse_with_options(sdp, &crate::ParseOptions::lenient()).is_ok()
+        );
+
+        let (session_description, diagnostics) = SessionDescription::parse_with_diagnostics(sdp);
+        assert_eq!(
+            session_description.map(|s| s.times.first().repeat.len()),
+            Some(1)
+        );
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| (d.severity, d.line))
+                .collect::<Vec<_>>(),
+            vec![(crate::Severity::Error, Some(5))]
+        );
+    }
+
+    #[test]
+    fn parse_with_options5() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+            "a=control:rtsp://example.com/a b\r\n",
+        );
+
+        let error = SessionDescription::try_from(sdp).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidUri);
+        assert_eq!(error.line(), Some(6));
+        assert_eq!(
+            SessionDescription::parse_with_options(sdp, &Default::default()).map_err(|e| e.kind),
+            Err(ErrorKind::InvalidUri)
+        );
+        assert!(
+            SessionDescription::parse_with_options(sdp, &crate::ParseOptions::lenient()).is_ok()
+        );
+
+        let sdp = sdp.replace("a=control:rtsp://example.com/a b", "a=extmap:1 urn:a%zz");
+        assert_eq!(
+            SessionDescription::from_bytes(sdp.as_bytes()).map_err(|e| e.kind),
+            Err(ErrorKind::InvalidUri)
+        );
+
+        let sdp = sdp
+            .replace("a=extmap:1 urn:a%zz\r\n", "")
+            .replace("s=-\r\n", "s=-\r\nu=http://example.com/a b\r\n");
+        assert_eq!(
+            SessionDescription::try_from(sdp.as_str()).map_err(|e| e.kind),
+            Err(ErrorKind::InvalidUri)
+        );
+    }
+
+    #[test]
+    fn parse_with_options6() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+            "c=IN IP4 2001:db8::1\r\n",
+        );
+
+        let error = SessionDescription::try_from(sdp).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidAddress);
+        assert_eq!(error.line(), Some(6));
+        assert_eq!(error.column(), Some(10));
+
+        let (session_description, _) =
+            SessionDescription::parse_with_options(sdp, &crate::ParseOptions::lenient()).unwrap();
+        assert_eq!(
+            session_description.media_descriptions[0].connections[0]
+                .connection_address
+                .base,
+            lines::common::Address::Ip("2001:db8::1".parse().unwrap())
+        );
+
+        let sdp = sdp.replace("IN IP4 10.47.16.5", "IN IP6 10.47.16.5");
+        let error = SessionDescription::try_from(sdp.as_str()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::InvalidAddress);
+        assert_eq!(error.line(), Some(2));
+    }
+
+    #[test]
+    fn parse_with_diagnostics1() {
+        use crate::Severity;
+
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "x=unknown\r\n",
+            "t=0 0\r\n",
+            "a=rtpmap:foo\r\n",
+            "m=audio 4917o RTP/AVP 0\r\n",
+            "a=rtpmap:0 PCMU/8000\r\n",
+            "m=video 51372 RTP/AVP 99\r\n",
+            "a=foo:bar\r\n",
+        );
+
+        let (session_description, diagnostics) = SessionDescription::parse_with_diagnostics(sdp);
+        let session_description = session_description.expect("session description");
+
+        assert_eq!(
+            session_description.attributes,
+            vec![lines::Attribute::Other("rtpmap".into(), Some("foo".into()))]
+        );
+        assert_eq!(session_description.media_descriptions.len(), 1);
+        assert_eq!(session_description.media_descriptions[0].media.port, 51372);
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| (d.severity, d.line))
+                .collect::<Vec<_>>(),
+            vec![
+                (Severity::Warning, Some(4)),
+                (Severity::Error, Some(6)),
+                (Severity::Error, Some(7)),
+                (Severity::Info, Some(10)),
+            ]
+        );
+        assert_eq!(
+            diagnostics[2].error.as_ref().map(Error::kind),
+            Some(&ErrorKind::InvalidNumber)
+        );
+    }
+
+    #[test]
+    fn parse_with_diagnostics2() {
+        let sdp = concat!("v=0\r\n", "s=-\r\n", "t=0 0\r\n",);
+
+        let (session_description, diagnostics) = SessionDescription::parse_with_diagnostics(sdp);
+
+        assert!(session_description.is_none());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, crate::Severity::Error);
+        assert_eq!(
+            diagnostics[0].error.as_ref().map(Error::kind),
+            Some(&ErrorKind::MissingLine)
+        );
+    }
+
+    #[test]
+    fn parse_with_diagnostics3() {
+        let sdp = concat!(
+            "v=0\r",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r",
+            "s=-\r",
+            "x=unknown\r",
+            "t=0 0\n",
+            "m=audio 4917o RTP/AVP 0\r\n",
+            "m=video 51372 RTP/AVP 99\r",
+        );
+
+        let (session_description, diagnostics) = SessionDescription::parse_with_diagnostics(sdp);
+
+        assert_eq!(
+            session_description.map(|s| s.media_descriptions.len()),
+            Some(1)
+        );
+        assert_eq!(
+            diagnostics.iter().map(|d| d.line).collect::<Vec<_>>(),
+            vec![Some(4), Some(6)]
+        );
+    }
+
+    #[test]
+    fn from_bytes1() {
+        let sdp = b"v=0\r\no=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\ns=Caf\xe9\r\nt=0 0\r\na=charset:ISO-8859-1\r\nm=audio 49170 RTP/AVP 0\r\ni=d\xe9j\xe0 vu\r\n";
+
+        let session_description = SessionDescription::from_bytes(sdp).unwrap();
+
+        assert_eq!(session_description.session_name.value(), "Café");
+        assert_eq!(
+            session_description.session_name.raw(),
+            Some(&b"Caf\xe9"[..])
+        );
+        assert_eq!(
+            session_description.media_descriptions[0]
+                .info
+                .as_ref()
+                .map(|info| info.value()),
+            Some("déjà vu")
+        );
+    }
+
+    #[test]
+    fn from_bytes4() {
+        let sdp = "v=0\r\no=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\ns=Café\r\nt=0 0\r\na=charset:ISO-8859-1\r\nm=audio 49170 RTP/AVP 0\r\ni=Café\r\na=charset:UTF-8\r\n";
+
+        let session_description = SessionDescription::from_bytes(sdp.as_bytes()).unwrap();
+
+        assert_eq!(session_description.session_name.value(), "CafÃ©");
+        assert_eq!(
+            session_description.session_name.raw(),
+            Some("Café".as_bytes())
+        );
+        assert_eq!(
+            session_description.media_descriptions[0]
+                .info
+                .as_ref()
+                .map(|info| (info.value(), info.raw())),
+            Some(("Café", None))
+        );
+    }
+
+    #[test]
+    fn from_bytes5() {
+        let sdp = "v=0\ro=Alice 2890844526 2890842807 IN IP4 10.47.16.5\rs=Café\rt=0 0\rm=audio 49170 RTP/AVP 0\ri=Café\ra=charset:ISO-8859-1\r";
+
+        let session_description = SessionDescription::from_bytes(sdp.as_bytes()).unwrap();
+
+        assert_eq!(session_description.session_name.value(), "Café");
+        assert_eq!(
+            session_description.media_descriptions[0]
+                .info
+                .as_ref()
+                .map(|info| info.value()),
+            Some("CafÃ©")
+        );
+    }
+
+    #[test]
+    fn from_bytes2() {
+        let sdp = "v=0\r\no=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\ns=Café\r\nt=0 0\r\n";
+
+        let session_description = SessionDescription::from_bytes(sdp.as_bytes()).unwrap();
+
+        assert_eq!(session_description.session_name.value(), "Café");
+        assert_eq!(session_description.session_name.raw(), None);
+        assert_eq!(Ok(session_description), SessionDescription::try_from(sdp));
+    }
+
+    #[test]
+    fn from_bytes3() {
+        let sdp = b"v=0\r\no=Alice 2890844526 2890842807 IN IP4 10.47.16.\xe9\r\ns=-\r\nt=0 0\r\n";
+
+        let error = SessionDescription::from_bytes(sdp).unwrap_err();
+
+        assert_eq!(error.kind(), &ErrorKind::InvalidLine);
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.column(), Some(47));
+    }
+
+    #[test]
+    fn from_bytes6() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+            "m=audio 49170 RTP/AVP 0 8 101\r\n",
+            "a=mptime:20 x -\r\n",
+        );
+
+        let error = SessionDescription::from_bytes(sdp.as_bytes()).unwrap_err();
+
+        assert_eq!(error.line(), Some(6));
+        assert_eq!(error.column(), Some(13));
+    }
+
+    #[test]
+    fn parse_incremental1() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+        );
+
+        assert_eq!(
+            SessionDescription::parse_incremental(sdp.as_bytes()),
+            SessionDescription::try_from(sdp)
+        );
+        assert_eq!(
+            SessionDescription::parse_incremental(&sdp.as_bytes()[..sdp.len() - 1])
+                .map_err(|e| e.kind),
+            Err(ErrorKind::Incomplete { needed: Some(1) })
+        );
+        assert_eq!(
+            SessionDescription::parse_incremental(&sdp.as_bytes()[..sdp.find("t=").unwrap()])
+                .map_err(|e| e.kind),
+            Err(ErrorKind::Incomplete { needed: None })
+        );
+        assert_eq!(
+            SessionDescription::parse_incremental(b"").map_err(|e| e.kind),
+            Err(ErrorKind::Incomplete { needed: Some(3) })
+        );
+    }
+
+    #[test]
+    fn parse_incremental2() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "s=-\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "t=0 0\r\n",
+        );
+
+        let error = SessionDescription::parse_incremental(sdp.as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), &ErrorKind::MissingLine);
+    }
+
+    #[test]
+    fn parse_incremental3() {
+        let sdp = concat!(
+            "v=0\r",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r",
+            "s=-\r",
+            "t=0 0\r",
+        );
+
+        assert!(SessionDescription::parse_incremental(sdp.as_bytes()).is_ok());
+        assert_eq!(
+            SessionDescription::parse_incremental(&sdp.as_bytes()[..sdp.find("t=").unwrap()])
+                .map_err(|e| e.kind),
+            Err(ErrorKind::Incomplete { needed: None })
+        );
+    }
+
+    #[test]
+    fn limits1() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+            "m=video 51372 RTP/AVP 99\r\n",
+        );
+        let options = crate::ParseOptions::default().with_limits(crate::Limits {
+            max_media_descriptions: 1,
+            ..Default::default()
+        });
+
+        let error = SessionDescription::parse_with_options(sdp, &options).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &ErrorKind::LimitExceeded {
+                limit: crate::Limit::MediaDescriptions
+            }
+        );
+        assert_eq!(error.line(), Some(6));
+        assert!(SessionDescription::try_from(sdp).is_ok());
+        assert_eq!(
+            SessionDescription::try_from(format!("{}a={}\r\n", sdp, "x".repeat(5000)))
+                .map_err(|e| e.kind),
+            Err(ErrorKind::LimitExceeded {
+                limit: crate::Limit::LineLength
+            })
+        );
+    }
+
+    #[test]
+    fn limits2() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+            "m=video 51372 RTP/AVP 99\r\n",
+        );
+        let limits = crate::Limits {
+            max_media_descriptions: 1,
+            ..Default::default()
+        };
+        let exceeded = Err(ErrorKind::LimitExceeded {
+            limit: crate::Limit::MediaDescriptions,
+        });
+
+        assert_eq!(
+            SessionDescription::from_bytes_with_options(
+                sdp.as_bytes(),
+                &crate::ParseOptions::default().with_limits(limits)
+            )
+            .map(|(session_description, _)| session_description)
+            .map_err(|e| e.kind),
+            exceeded
+        );
+        assert_eq!(
+            SessionDescription::from_bytes_with_options(
+                sdp.as_bytes(),
+                &crate::ParseOptions::lenient()
+            )
+            .map(|(session_description, _)| session_description),
+            SessionDescription::from_bytes(sdp.as_bytes())
+        );
+
+        let (session_description, diagnostics) =
+            SessionDescription::parse_with_diagnostics_and_limits(sdp, &limits);
+        assert!(session_description.is_none());
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| d.error.as_ref().map(|e| e.kind.clone()))
+                .collect::<Vec<_>>(),
+            vec![exceeded.err()]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde1() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "c=IN IP4 224.2.17.12/127\r\n",
+            "t=2873397496 2873404696\r\n",
+            "r=7d 1h 0 25h\r\n",
+            "a=recvonly\r\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+            "a=rtpmap:0 PCMU/8000\r\n",
+        );
+        let session_description = SessionDescription::try_from(sdp).unwrap();
+
+        let json = serde_json::to_value(&session_description).unwrap();
+        assert_eq!(json["origin"]["sess_id"], serde_json::json!(2890844526u64));
+        assert_eq!(json["connection"]["connection_address"]["ttl"], 127);
+        assert_eq!(
+            json["times"][0]["repeat"][0]["interval"],
+            serde_json::json!({"unit": "days", "seconds": 604800})
+        );
+        assert_eq!(
+            json["media_descriptions"][0]["attributes"][0]["name"],
+            "rtpmap"
+        );
+        assert!(json.get("unknown_lines").is_none());
+        assert_eq!(
+            serde_json::from_value::<SessionDescription>(json.clone()).unwrap(),
+            session_description
+        );
+
+        let mut json = json;
+        json["times"] = serde_json::json!([]);
+        assert!(serde_json::from_value::<SessionDescription>(json).is_err());
+    }
+
+    #[test]
+    fn unknown_lines1() {
+        let sdp = concat!(
+            "v=0\r\n",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+            "x=unknown\r\n",
+            "a=recvonly\r\n",
+            "m=video 51372 RTP/AVP 99\r\n",
+            "y=unknown\r\n",
+        );
+
+        let (parsed_sdp, _) = SessionDescription::parse_with_options(
+            sdp,
+            &crate::ParseOptions::strict().collecting_unknown_lines(),
+        )
+        .unwrap();
+        assert_eq!(parsed_sdp.media_descriptions.len(), 2);
+        assert_eq!(
+            parsed_sdp.unknown_lines,
+            vec!["x=unknown".to_string(), "y=unknown".to_string()]
+        );
+    }
+
+    #[test]
+    fn unknown_lines2() {
+        let sdp = concat!(
+            "v=0\r",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r",
+            "s=-\r",
+            "x=unknown\r",
+            "t=0 0\n",
+            "m=audio 49170 RTP/AVP 0\r\n",
+            "y=unknown\r",
+            "a=recvonly\r",
+        );
+
+        let (parsed_sdp, _) = SessionDescription::parse_with_options(
+            sdp,
+            &crate::ParseOptions::strict().collecting_unknown_lines(),
+        )
+        .unwrap();
+        assert_eq!(parsed_sdp.media_descriptions.len(), 1);
+        assert_eq!(
+            parsed_sdp.unknown_lines,
+            vec!["x=unknown".to_string(), "y=unknown".to_string()]
+        );
+    }
 }
@@ -0,0 +1,225 @@
+use crate::{
+    error::located,
+    lines::{self, attribute::AttributeRef},
+    session_description::{ensure_consumed, Tokenizer},
+    Error, MediaDescriptionRef, SessionDescription,
+};
+use std::convert::TryFrom;
+
+/// A borrowed Session description. Unlike [SessionDescription], it doesn't allocate a `String` for
+/// every line and attribute: it keeps the tokens (`&'a str` slices) of the SDP message and parses
+/// them only when asked for, which makes it well suited for reading a few values out of many SDP
+/// messages. Use [SessionDescriptionRef::into_owned] to get the (owned) [SessionDescription].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SessionDescriptionRef<'a> {
+    tokenizer: Tokenizer<'a>,
+    media_descriptions: Vec<MediaDescriptionRef<'a>>,
+}
+
+impl<'a> SessionDescriptionRef<'a> {
+    /// Tokenizes the given SDP message. Lines are only checked for their syntax, their values are
+    /// parsed by the relevant accessor. The default [crate::Limits] apply, use
+    /// [SessionDescriptionRef::parse_with_limits] for anything else.
+    pub fn parse(input: &'a str) -> Result<Self, Error> {
+        Self::parse_with_limits(input, &Default::default())
+    }
+
+    pub fn parse_with_limits(input: &'a str, limits: &crate::Limits) -> Result<Self, Error> {
+        let parse = || {
+            limits.check(input.as_bytes())?;
+            let (rem, tokenizer) = Tokenizer::tokenize(input)?;
+            ensure_consumed(input, rem)?;
+            Ok(Self::from(tokenizer))
+        };
+
+        parse().map_err(|error: Error| error.locate(input))
+    }
+
+    pub fn version(&self) -> &'a str {
+        self.tokenizer.version.value
+    }
+
+    pub fn origin(&self) -> Result<lines::Origin, Error> {
+        located(
+            self.tokenizer.origin.username,
+            self.tokenizer.origin.clone(),
+        )
+    }
+
+    pub fn session_name(&self) -> &'a str {
+        self.tokenizer.session_name.value
+    }
+
+    pub fn session_info(&self) -> Option<&'a str> {
+        self.tokenizer.session_info.as_ref().map(|info| info.value)
+    }
+
+    pub fn uri(&self) -> Option<&'a str> {
+        self.tokenizer.uri.as_ref().map(|uri| uri.value)
+    }
+
+    pub fn emails(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.tokenizer.emails.iter().map(|email| email.value)
+    }
+
+    pub fn phones(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.tokenizer.phones.iter().map(|phone| phone.value)
+    }
+
+    pub fn connection(&self) -> Option<Result<lines::Connection, Error>> {
+        self.tokenizer
+            .connection
+            .as_ref()
+            .map(|connection| located(connection.nettype, connection.clone()))
+    }
+
+    pub fn bandwidths(&self) -> impl Iterator<Item = Result<lines::Bandwidth, Error>> + '_ {
+        self.tokenizer
+            .bandwidths
+            .iter()
+            .map(|bandwidth| located(bandwidth.key, bandwidth.clone()))
+    }
+
+    pub fn times(&self) -> impl Iterator<Item = Result<crate::Time, Error>> + '_ {
+        self.tokenizer
+            .times
+            .iter()
+            .map(|time| crate::Time::try_from(time.clone()))
+    }
+
+    pub fn key(&self) -> Option<lines::Key> {
+        self.tokenizer.key.clone().map(Into::into)
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = AttributeRef<'a>> + '_ {
+        self.tokenizer.attributes.iter().cloned().map(Into::into)
+    }
+
+    /// The first session level attribute with the given name, if any.
+    pub fn attribute(&self, name: &str) -> Option<AttributeRef<'a>> {
+        self.attributes().find(|attribute| attribute.name == name)
+    }
+
+    /// The session level direction, see [SessionDescription::direction].
+    pub fn direction(&self) -> lines::attribute::Direction {
+        lines::attribute::Direction::find_in_refs(self.attributes()).unwrap_or_default()
+    }
+
+    pub fn media_descriptions(&self) -> &[MediaDescriptionRef<'a>] {
+        &self.media_descriptions
+    }
+
+    pub fn into_owned(self) -> Result<SessionDescription, Error> {
+        SessionDescription::try_from(Tokenizer {
+            media_descriptions: self
+                .media_descriptions
+                .into_iter()
+                .map(MediaDescriptionRef::into_tokenizer)
+                .collect(),
+            ..self.tokenizer
+        })
+    }
+}
+
+impl<'a> From<Tokenizer<'a>> for SessionDescriptionRef<'a> {
+    fn from(mut tokenizer: Tokenizer<'a>) -> Self {
+        let media_descriptions = std::mem::take(&mut tokenizer.media_descriptions)
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Self {
+            tokenizer,
+            media_descriptions,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for SessionDescriptionRef<'a> {
+    type Error = Error;
+
+    fn try_from(from: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDP: &str = concat!(
+        "v=0\r\n",
+        "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+        "s=-\r\n",
+        "c=IN IP4 10.47.16.5\r\n",
+        "t=0 0\r\n",
+        "a=recvonly\r\n",
+        "m=audio 49170 RTP/AVP 0 99\r\n",
+        "a=rtpmap:99 h263-1998/90000\r\n",
+        "m=video 51372 RTP/AVP 99\r\n",
+        "a=ptime:foo\r\n",
+    );
+
+    #[test]
+    fn parse1() {
+        let session_description = SessionDescriptionRef::parse(SDP).unwrap();
+
+        assert_eq!(session_description.version(), "0");
+        assert_eq!(session_description.session_name(), "-");
+        assert_eq!(
+            session_description.origin().map(|origin| origin.username),
+            Ok("Alice".into())
+        );
+        assert_eq!(
+            session_description.direction(),
+            lines::attribute::Direction::Recvonly
+        );
+        assert_eq!(session_description.media_descriptions().len(), 2);
+        assert_eq!(
+            session_description.media_descriptions()[0]
+                .rtpmap(99)
+                .map(|r| r.map(|r| r.clock_rate)),
+            Some(Ok(90000))
+        );
+        assert_eq!(
+            session_description.media_descriptions()[1]
+                .attribute("ptime")
+                .map(|a| a.value),
+            Some(Some("foo"))
+        );
+    }
+
+    #[test]
+    fn parse2() {
+        let limits = crate::Limits {
+            max_media_descriptions: 1,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            SessionDescriptionRef::parse_with_limits(SDP, &limits).map_err(|e| e.kind),
+            Err(crate::ErrorKind::LimitExceeded {
+                limit: crate::Limit::MediaDescriptions
+            })
+        );
+        assert!(SessionDescriptionRef::parse_with_limits(SDP, &crate::Limits::unlimited()).is_ok());
+    }
+
+    #[test]
+    fn into_owned1() {
+        let sdp = &SDP[..SDP.find("m=video").unwrap()];
+        let session_description = SessionDescriptionRef::parse(sdp).unwrap();
+
+        assert_eq!(
+            session_description.into_owned(),
+            SessionDescription::try_from(sdp)
+        );
+    }
+
+    #[test]
+    fn into_owned2() {
+        let session_description = SessionDescriptionRef::parse(SDP).unwrap();
+
+        assert!(session_description.into_owned().is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! The "string form" serde representation, where a [SessionDescription], a [MediaDescription] or
+//! a [Time] is serialized as the list of its SDP lines (without line endings), like
+//! `["v=0", "o=- 1 1 IN IP4 127.0.0.1", "s=-", "t=0 0"]`, instead of the typed representation of
+//! the derived serde impls. Deserializing parses the lines back. Only available with the `serde`
+//! feature.
+//!
+//! It can be used either through the [StringForm] wrapper or as a field attribute:
+//! `#[serde(with = "sdp_rs::string_form")]`.
+
+use crate::{Error, MediaDescription, SessionDescription, Time};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+
+/// The types that have a string form: these can be written as SDP lines and parsed back.
+pub trait Lines: std::fmt::Display + Sized {
+    fn parse_lines(lines: &str) -> Result<Self, Error>;
+}
+
+impl Lines for SessionDescription {
+    fn parse_lines(lines: &str) -> Result<Self, Error> {
+        Self::try_from(lines)
+    }
+}
+
+impl Lines for MediaDescription {
+    fn parse_lines(lines: &str) -> Result<Self, Error> {
+        let (rem, tokenizer) = crate::tokenizers::media_description::Tokenizer::tokenize(lines)?;
+        crate::session_description::ensure_consumed(lines, rem)?;
+
+        Self::try_from(tokenizer).map_err(|error| error.locate(lines))
+    }
+}
+
+impl Lines for Time {
+    fn parse_lines(lines: &str) -> Result<Self, Error> {
+        let (rem, tokenizer) = crate::tokenizers::time::Tokenizer::tokenize(lines)?;
+        crate::session_description::ensure_consumed(lines, rem)?;
+
+        Self::try_from(tokenizer).map_err(|error| error.locate(lines))
+    }
+}
+
+/// A wrapper that (de)serializes the inner type in its string form.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StringForm<T>(pub T);
+
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Lines,
+    S: Serializer,
+{
+    serializer.collect_seq(value.to_string().split_terminator("\r\n"))
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Lines,
+    D: Deserializer<'de>,
+{
+    let lines = Vec::<String>::deserialize(deserializer)?;
+    let mut text = String::with_capacity(lines.iter().map(|line| line.len() + 2).sum());
+    for line in lines {
+        text.push_str(&line);
+        text.push_str("\r\n");
+    }
+
+    T::parse_lines(&text).map_err(D::Error::custom)
+}
+
+impl<T: Lines> Serialize for StringForm<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: Lines> Deserialize<'de> for StringForm<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDP: &str = concat!(
+        "v=0\r\n",
+        "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+        "s=-\r\n",
+        "t=2873397496 2873404696\r\n",
+        "r=7d 1h 0 25h\r\n",
+        "m=audio 49170 RTP/AVP 0\r\n",
+        "a=ptime:20\r\n",
+    );
+
+    #[test]
+    fn serialize1() {
+        let session_description = SessionDescription::try_from(SDP).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(StringForm(session_description.clone())).unwrap(),
+            serde_json::json!([
+                "v=0",
+                "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5",
+                "s=-",
+                "t=2873397496 2873404696",
+                "r=7d 1h 0 25h",
+                "m=audio 49170 RTP/AVP 0",
+                "a=ptime:20"
+            ])
+        );
+        assert_eq!(
+            serde_json::to_value(StringForm(session_description.times.first().clone())).unwrap(),
+            serde_json::json!(["t=2873397496 2873404696", "r=7d 1h 0 25h"])
+        );
+    }
+
+    #[test]
+    fn deserialize1() {
+        let session_description = SessionDescription::try_from(SDP).unwrap();
+        let json = serde_json::to_string(&StringForm(session_description.clone())).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<StringForm<SessionDescription>>(&json).unwrap(),
+            StringForm(session_description.clone())
+        );
+
+        let media_description = session_description.media_descriptions[0].clone();
+        let json = serde_json::to_string(&StringForm(media_description.clone())).unwrap();
+        assert_eq!(
+            serde_json::from_str::<StringForm<MediaDescription>>(&json).unwrap(),
+            StringForm(media_description)
+        );
+    }
+
+    #[test]
+    fn deserialize2() {
+        assert!(serde_json::from_str::<StringForm<SessionDescription>>(r#"["v=0"]"#).is_err());
+    }
+}
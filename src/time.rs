@@ -1,8 +1,13 @@
 use crate::{
+    error::located,
     lines::{Active, Repeat, Zone},
     Error,
 };
-use std::convert::{TryFrom, TryInto};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::{
+    convert::{TryFrom, TryInto},
+    iter::Peekable,
+};
 
 /// The time high level type tokenizer. It tokenizes all lines related to time (`a=`, `r=`, `z=`)
 /// at once.
@@ -12,24 +17,245 @@ pub use crate::tokenizers::time::Tokenizer;
 
 /// The time high level type. This type holds all types related to the time in SDP.
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(crate = "serde")
+)]
 pub struct Time {
     pub active: Active,
     pub repeat: Vec<Repeat>,
     pub zone: Option<Zone>,
 }
 
+impl Time {
+    /// A time active between the given start and stop times, without any repeat or zone.
+    pub fn between(start: DateTime<Utc>, stop: DateTime<Utc>) -> Result<Self, Error> {
+        Ok(Self {
+            active: (Some(start), Some(stop)).try_into()?,
+            repeat: vec![],
+            zone: None,
+        })
+    }
+
+    /// A time active from the given start time onwards (`t=<start> 0`).
+    pub fn starting_at(start: DateTime<Utc>) -> Result<Self, Error> {
+        Ok(Self {
+            active: (Some(start), None).try_into()?,
+            repeat: vec![],
+            zone: None,
+        })
+    }
+
+    /// A permanent time (`t=0 0`).
+    pub fn permanent() -> Self {
+        Self {
+            active: Active { start: 0, stop: 0 },
+            repeat: vec![],
+            zone: None,
+        }
+    }
+
+    /// Expands the time into the concrete `(start, end)` intervals during which the session is
+    /// active, ordered by start. Without repeat lines (or without a start time) that's just the
+    /// active window. With repeat lines, each interval starts at `start + n * interval + offset`
+    /// and lasts `duration`, as long as it starts before the stop time. Intervals are then
+    /// shifted by the zone adjustment in effect at the time they would start.
+    ///
+    /// Unbounded start and stop times are given as `chrono::DateTime::<Utc>::MIN_UTC` and
+    /// `chrono::DateTime::<Utc>::MAX_UTC`, hence for unbounded repeated times the iterator only
+    /// ends once the occurrences overflow the range of `chrono::DateTime`.
+    pub fn occurrences(&self) -> impl Iterator<Item = (DateTime<Utc>, DateTime<Utc>)> + '_ {
+        let stop = self.active.stop_time().unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+        let streams = match self.active.start_time() {
+            Some(start) if !self.repeat.is_empty() => self
+                .repeat
+                .iter()
+                .map(|repeat| self.repeat_occurrences(repeat, start, stop))
+                .collect(),
+            start => {
+                vec![Box::new(std::iter::once((
+                    start.unwrap_or(DateTime::<Utc>::MIN_UTC),
+                    stop,
+                ))) as Intervals]
+            }
+        };
+
+        Occurrences {
+            streams: streams.into_iter().map(Iterator::peekable).collect(),
+        }
+    }
+
+    /// Whether the time is active at the given instant, that is whether one of its
+    /// [Time::occurrences] contains it. The occurrence of each repeat line that may contain the
+    /// instant is computed directly, instead of going through all the occurrences before it.
+    pub fn is_active_at(&self, instant: DateTime<Utc>) -> bool {
+        let stop = self.active.stop_time().unwrap_or(DateTime::<Utc>::MAX_UTC);
+        let start = match self.active.start_time() {
+            Some(start) if !self.repeat.is_empty() => start,
+            start => return start.map_or(true, |start| start <= instant) && instant < stop,
+        };
+
+        //for each offset, the occurrences only get later (and so do their ends) as long as the
+        //zone adjustment stays the same: the latest one starting before the instant (given any
+        //of the adjustments), before the stop time and before the next adjustment time is the
+        //one that may contain the instant
+        let parts = self.zone.iter().flat_map(|zone| zone.parts.iter());
+        let last = match stop.timestamp_subsec_nanos() {
+            0 => stop.timestamp() - 1,
+            _ => stop.timestamp(),
+        };
+        let adjustment_bounds = parts
+            .clone()
+            .filter_map(|part| crate::lines::active::from_ntp(part.adjustment_time))
+            .map(|adjustment_time| adjustment_time.timestamp() - 1)
+            .chain(std::iter::once(last))
+            .collect::<Vec<_>>();
+        let bounds = parts
+            .map(|part| Duration::from(part.offset).num_seconds())
+            .chain(std::iter::once(0))
+            .filter_map(|offset| instant.timestamp().checked_sub(offset))
+            .flat_map(|bound| {
+                adjustment_bounds
+                    .iter()
+                    .map(move |adjustment_bound| bound.min(*adjustment_bound).min(last))
+            })
+            .collect::<Vec<_>>();
+
+        self.repeat.iter().any(|repeat| {
+            let interval = Duration::from(repeat.interval).num_seconds();
+            let duration: Duration = repeat.duration.into();
+            repeat_offsets(repeat).into_iter().any(|offset| {
+                bounds.iter().any(|bound| {
+                    let n = match bound
+                        .checked_sub(start.timestamp())
+                        .and_then(|delta| delta.checked_sub(offset))
+                    {
+                        Some(delta) if delta >= 0 && interval > 0 => delta / interval,
+                        Some(delta) if delta >= 0 => 0,
+                        _ => return false,
+                    };
+                    matches!(
+                        repeat_base(start, interval, n, offset)
+                            .filter(|base| *base < stop)
+                            .and_then(|base| self.shifted(base, duration)),
+                        Some((start, end)) if start <= instant && instant < end
+                    )
+                })
+            })
+        })
+    }
+
+    fn repeat_occurrences<'a>(
+        &'a self,
+        repeat: &'a Repeat,
+        start: DateTime<Utc>,
+        stop: DateTime<Utc>,
+    ) -> Intervals<'a> {
+        let interval = Duration::from(repeat.interval).num_seconds();
+        let duration: Duration = repeat.duration.into();
+        let offsets = repeat_offsets(repeat);
+
+        //stops at the first occurrence that overflows, since the following ones overflow too
+        Box::new(
+            (0i64..)
+                .take_while(move |n| *n == 0 || interval > 0)
+                .flat_map(move |n| offsets.clone().into_iter().map(move |o| (n, o)))
+                .scan((), move |_, (n, offset)| {
+                    repeat_base(start, interval, n, offset)
+                })
+                .take_while(move |base| *base < stop)
+                .filter_map(move |base| self.shifted(base, duration)),
+        )
+    }
+
+    //the occurrence starting at the given base time, shifted by the zone adjustment in effect
+    fn shifted(&self, base: DateTime<Utc>, duration: Duration) -> Option<Interval> {
+        let start = base.checked_add_signed(self.zone_offset_at(base))?;
+        Some((start, start.checked_add_signed(duration)?))
+    }
+
+    fn zone_offset_at(&self, instant: DateTime<Utc>) -> Duration {
+        self.zone
+            .iter()
+            .flat_map(|zone| zone.parts.iter())
+            .rev()
+            .find(|part| {
+                matches!(
+                    crate::lines::active::from_ntp(part.adjustment_time),
+                    Some(adjustment_time) if adjustment_time <= instant
+                )
+            })
+            .map(|part| part.offset.into())
+            .unwrap_or_else(Duration::zero)
+    }
+}
+
+//the (sorted) offsets of a repeat line in seconds, a repeat line without offsets starting at the
+//beginning of each interval
+fn repeat_offsets(repeat: &Repeat) -> Vec<i64> {
+    let mut offsets = repeat
+        .offsets
+        .iter()
+        .map(|offset| Duration::from(*offset).num_seconds())
+        .collect::<Vec<_>>();
+    if offsets.is_empty() {
+        offsets.push(0);
+    }
+    offsets.sort_unstable();
+    offsets
+}
+
+//the (not yet zone adjusted) start of the n-th interval of a repeat line at the given offset, or
+//`None` if it overflows
+fn repeat_base(start: DateTime<Utc>, interval: i64, n: i64, offset: i64) -> Option<DateTime<Utc>> {
+    let delta = interval.checked_mul(n)?.checked_add(offset)?;
+    Utc.timestamp_opt(start.timestamp().checked_add(delta)?, 0)
+        .single()
+}
+
+type Interval = (DateTime<Utc>, DateTime<Utc>);
+type Intervals<'a> = Box<dyn Iterator<Item = Interval> + 'a>;
+
+/// Merges the (ordered) occurrences of each repeat line into a single ordered iterator.
+struct Occurrences<'a> {
+    streams: Vec<Peekable<Intervals<'a>>>,
+}
+
+impl<'a> Iterator for Occurrences<'a> {
+    type Item = Interval;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self
+            .streams
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, stream)| stream.peek().map(|(start, _)| (*start, index)))
+            .min()?;
+
+        self.streams[next.1].next()
+    }
+}
+
 impl<'a> TryFrom<Tokenizer<'a>> for Time {
     type Error = Error;
 
     fn try_from(tokenizer: Tokenizer<'a>) -> Result<Self, Self::Error> {
         Ok(Self {
-            active: tokenizer.active.try_into()?,
+            active: located(tokenizer.active.start, tokenizer.active)?,
             repeat: tokenizer
                 .repeat
                 .into_iter()
-                .map(TryInto::try_into)
+                .map(|repeat| located(repeat.interval, repeat))
                 .collect::<Result<Vec<_>, _>>()?,
-            zone: tokenizer.zone.map(TryInto::try_into).transpose()?,
+            zone: tokenizer
+                .zone
+                .map(|zone| {
+                    let token = zone.parts.first().map_or("", |part| part.adjustment);
+                    located(token, zone)
+                })
+                .transpose()?,
         })
     }
 }
@@ -124,6 +350,119 @@ mod tests {
         );
     }
 
+    #[test]
+    fn between1() {
+        use chrono::TimeZone;
+
+        let time = Time::between(
+            Utc.with_ymd_and_hms(2018, 1, 8, 10, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2018, 1, 8, 11, 0, 0).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(time.to_string(), "t=3724394400 3724398000");
+        assert_eq!(Time::permanent().to_string(), "t=0 0");
+    }
+
+    #[test]
+    fn occurrences1() {
+        use chrono::TimeZone;
+
+        let tokenizer = Tokenizer {
+            active: ("3724394400", "3725604000").into(),
+            repeat: vec![("7d", "1h", vec!["25h", "0"]).into()],
+            zone: Some(vec![("3724999200", "-1h")].into()),
+        };
+        let time = Time::try_from(tokenizer).unwrap();
+
+        assert_eq!(
+            time.occurrences().collect::<Vec<_>>(),
+            vec![
+                (
+                    Utc.with_ymd_and_hms(2018, 1, 8, 10, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2018, 1, 8, 11, 0, 0).unwrap()
+                ),
+                (
+                    Utc.with_ymd_and_hms(2018, 1, 9, 11, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2018, 1, 9, 12, 0, 0).unwrap()
+                ),
+                (
+                    Utc.with_ymd_and_hms(2018, 1, 15, 9, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2018, 1, 15, 10, 0, 0).unwrap()
+                ),
+                (
+                    Utc.with_ymd_and_hms(2018, 1, 16, 10, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2018, 1, 16, 11, 0, 0).unwrap()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn occurrences2() {
+        assert_eq!(
+            Time::permanent().occurrences().collect::<Vec<_>>(),
+            vec![(DateTime::<Utc>::MIN_UTC, DateTime::<Utc>::MAX_UTC)]
+        );
+    }
+
+    #[test]
+    fn occurrences3() {
+        let time = Time {
+            active: Active {
+                start: 3724394400,
+                stop: 0,
+            },
+            repeat: vec![Repeat {
+                interval: TypedTime::Days(Duration::seconds(i64::MAX / 1000)),
+                duration: TypedTime::Hours(Duration::hours(1)),
+                offsets: vec![],
+            }],
+            zone: None,
+        };
+
+        assert_eq!(time.occurrences().count(), 1);
+    }
+
+    #[test]
+    fn is_active_at1() {
+        let tokenizer = Tokenizer {
+            active: ("3724394400", "3725604000").into(),
+            repeat: vec![("7d", "1h", vec!["25h", "0"]).into()],
+            zone: Some(vec![("3724999200", "-1h")].into()),
+        };
+        let time = Time::try_from(tokenizer).unwrap();
+        let first = time.active.start_time().unwrap() - Duration::hours(2);
+
+        for minutes in (0..24 * 60 * 15).step_by(10) {
+            let instant = first + Duration::minutes(minutes);
+            assert_eq!(
+                time.is_active_at(instant),
+                time.occurrences()
+                    .any(|(start, end)| start <= instant && instant < end),
+                "{}",
+                instant
+            );
+        }
+    }
+
+    #[test]
+    fn is_active_at2() {
+        use chrono::TimeZone;
+
+        let tokenizer = Tokenizer {
+            active: ("3724394400", "0").into(),
+            repeat: vec![("1d", "1h", vec!["0"]).into()],
+            zone: None,
+        };
+        let time = Time::try_from(tokenizer).unwrap();
+
+        assert!(time.is_active_at(Utc.with_ymd_and_hms(200000, 1, 10, 10, 30, 0).unwrap()));
+        assert!(!time.is_active_at(Utc.with_ymd_and_hms(200000, 1, 10, 11, 30, 0).unwrap()));
+        assert!(!time.is_active_at(Utc.with_ymd_and_hms(2018, 1, 7, 10, 30, 0).unwrap()));
+        assert!(Time::permanent().is_active_at(DateTime::<Utc>::MIN_UTC));
+    }
+
     #[test]
     fn display1() {
         let time = Time {
@@ -182,6 +521,6 @@ mod tests {
             zone: None,
         };
 
-        assert_eq!(time.to_string(), concat!("t=3724394400 3724398000",));
+        assert_eq!(time.to_string(), "t=3724394400 3724398000");
     }
 }
@@ -9,8 +9,9 @@ pub struct Tokenizer<'a> {
 impl<'a> Tokenizer<'a> {
     pub fn tokenize(part: &'a str) -> TResult<'a, Self> {
         use crate::parser_utils::*;
+        use nom::{branch::alt, combinator::rest};
 
-        let (params, fmt) = until_space(part)?;
+        let (params, fmt) = alt((until_space, rest))(part)?;
 
         Ok(("", Tokenizer { fmt, params }))
     }
@@ -41,4 +42,18 @@ mod tests {
             )),
         );
     }
+
+    #[test]
+    fn tokenizer2() {
+        assert_eq!(
+            Tokenizer::tokenize("101"),
+            Ok((
+                "",
+                Tokenizer {
+                    fmt: "101",
+                    params: ""
+                }
+            )),
+        );
+    }
 }
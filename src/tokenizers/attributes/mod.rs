@@ -1,3 +1,4 @@
 pub mod fmtp;
 pub mod name_optvalue;
 pub mod rtpmap;
+pub mod silence_supp;
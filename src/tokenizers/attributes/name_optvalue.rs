@@ -15,6 +15,8 @@ impl<'a> Tokenizer<'a> {
             return Err(nom::Err::Error(TokenizerError {
                 context: "break; nothing more to tokenize".into(),
                 nom_kind: None,
+                remaining: Some(0),
+                missing_line: false,
             }));
         }
 
@@ -54,7 +56,7 @@ mod tests {
 
     #[test]
     fn tokenizer1() {
-        let input = concat!("something");
+        let input = "something";
 
         assert_eq!(
             Tokenizer::tokenize(input),
@@ -70,7 +72,7 @@ mod tests {
 
     #[test]
     fn tokenizer2() {
-        let input = concat!("something;");
+        let input = "something;";
 
         assert_eq!(
             Tokenizer::tokenize(input),
@@ -86,7 +88,7 @@ mod tests {
 
     #[test]
     fn tokenizer3() {
-        let input = concat!("something=else");
+        let input = "something=else";
 
         assert_eq!(
             Tokenizer::tokenize(input),
@@ -102,7 +104,7 @@ mod tests {
 
     #[test]
     fn tokenizer4() {
-        let input = concat!("something=else;");
+        let input = "something=else;";
 
         assert_eq!(
             Tokenizer::tokenize(input),
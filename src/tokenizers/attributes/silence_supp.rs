@@ -0,0 +1,82 @@
+use crate::{TResult, TokenizerError};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Tokenizer<'a> {
+    pub enable: &'a str,
+    pub timer: &'a str,
+    pub pref: &'a str,
+    pub sid_use: &'a str,
+    pub fxnslevel: &'a str,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn tokenize(part: &'a str) -> TResult<'a, Self> {
+        use crate::parser_utils::*;
+
+        let (rem, enable) = until_space(part)?;
+        let (rem, timer) = until_space(rem)?;
+        let (rem, pref) = until_space(rem)?;
+        //sidUse could be `No SID`, `Fixed Noise` or `Sampled Noise`, hence it includes a space
+        let (sid_use, fxnslevel) = match rem.rfind(' ') {
+            Some(index) => (&rem[..index], &rem[index + 1..]),
+            None => {
+                return Err(TokenizerError::part_with_input("silenceSupp fxnslevel", rem).into())
+            }
+        };
+
+        Ok((
+            "",
+            Tokenizer {
+                enable,
+                timer,
+                pref,
+                sid_use,
+                fxnslevel,
+            },
+        ))
+    }
+}
+
+impl<'a> From<(&'a str, &'a str, &'a str, &'a str, &'a str)> for Tokenizer<'a> {
+    fn from(
+        (enable, timer, pref, sid_use, fxnslevel): (&'a str, &'a str, &'a str, &'a str, &'a str),
+    ) -> Self {
+        Self {
+            enable,
+            timer,
+            pref,
+            sid_use,
+            fxnslevel,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenizer1() {
+        let silence_supp = "off - - - -";
+
+        assert_eq!(
+            Tokenizer::tokenize(silence_supp),
+            Ok(("", ("off", "-", "-", "-", "-").into())),
+        );
+    }
+
+    #[test]
+    fn tokenizer2() {
+        let silence_supp = "on 50 standard Fixed Noise 30";
+
+        assert_eq!(
+            Tokenizer::tokenize(silence_supp),
+            Ok(("", ("on", "50", "standard", "Fixed Noise", "30").into())),
+        );
+    }
+
+    #[test]
+    fn tokenizer3() {
+        assert!(Tokenizer::tokenize("off - -").is_err());
+    }
+}
@@ -64,7 +64,7 @@ mod tests {
 
     #[test]
     fn connection_address_tokenizer1() {
-        let connection = concat!("198.51.100.1");
+        let connection = "198.51.100.1";
 
         assert_eq!(
             Tokenizer::tokenize(connection),
@@ -81,7 +81,7 @@ mod tests {
 
     #[test]
     fn connection_address_tokenizer2() {
-        let connection = concat!("233.252.0.1/127");
+        let connection = "233.252.0.1/127";
 
         assert_eq!(
             Tokenizer::tokenize(connection),
@@ -98,7 +98,7 @@ mod tests {
 
     #[test]
     fn connection_address_tokenizer3() {
-        let connection = concat!("233.252.0.1/127/2");
+        let connection = "233.252.0.1/127/2";
 
         assert_eq!(
             Tokenizer::tokenize(connection),
@@ -46,7 +46,7 @@ mod tests {
 
     #[test]
     fn connection_tokenizer1() {
-        let connection = concat!("c=IN IP4 198.51.100.1\r\nsomething");
+        let connection = "c=IN IP4 198.51.100.1\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(connection),
@@ -63,7 +63,7 @@ mod tests {
 
     #[test]
     fn connection_tokenizer2() {
-        let connection = concat!("c=IN IP4 233.252.0.1/127\r\nsomething");
+        let connection = "c=IN IP4 233.252.0.1/127\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(connection),
@@ -84,7 +84,7 @@ mod tests {
 
     #[test]
     fn connection_tokenizer3() {
-        let connection = concat!("c=IN IP4 233.252.0.1/127/2\r\nsomething");
+        let connection = "c=IN IP4 233.252.0.1/127/2\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(connection),
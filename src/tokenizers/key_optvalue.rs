@@ -55,7 +55,7 @@ mod tests {
 
     #[test]
     fn tokenizer1() {
-        let key_optvalue = concat!("a=recvonly\r\nsomething");
+        let key_optvalue = "a=recvonly\r\nsomething";
 
         assert_eq!(
             Tokenizer::<'a'>::tokenize(key_optvalue),
@@ -71,7 +71,7 @@ mod tests {
 
     #[test]
     fn tokenizer2() {
-        let key_optvalue = concat!("a=rtpmap:99 h263-1998/90000\r\nsomething");
+        let key_optvalue = "a=rtpmap:99 h263-1998/90000\r\nsomething";
 
         assert_eq!(
             Tokenizer::<'a'>::tokenize(key_optvalue),
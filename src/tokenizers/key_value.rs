@@ -36,7 +36,7 @@ mod tests {
 
     #[test]
     fn tokenizer() {
-        let key_value = concat!("b=CT:128\r\nsomething");
+        let key_value = "b=CT:128\r\nsomething";
 
         assert_eq!(
             Tokenizer::<'b'>::tokenize(key_value),
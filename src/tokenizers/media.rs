@@ -86,7 +86,7 @@ mod tests {
 
     #[test]
     fn tokenizer1() {
-        let media = concat!("m=audio 49170 RTP/AVP 0\r\nsomething");
+        let media = "m=audio 49170 RTP/AVP 0\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(media),
@@ -104,7 +104,7 @@ mod tests {
 
     #[test]
     fn tokenizer2() {
-        let media = concat!("m=video 49170/2 RTP/AVP 31\r\nsomething");
+        let media = "m=video 49170/2 RTP/AVP 31\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(media),
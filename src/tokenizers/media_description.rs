@@ -116,7 +116,7 @@ mod tests {
 
     #[test]
     fn tokenizer2() {
-        let part = concat!("m=audio 49170 RTP/AVP 0\r\nsomething",);
+        let part = "m=audio 49170 RTP/AVP 0\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(part),
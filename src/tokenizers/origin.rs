@@ -43,7 +43,7 @@ mod tests {
 
     #[test]
     fn tokenizer() {
-        let origin = concat!("o=jdoe 3724394400 3724394405 IN IP4 198.51.100.1\r\nsomething");
+        let origin = "o=jdoe 3724394400 3724394405 IN IP4 198.51.100.1\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(origin),
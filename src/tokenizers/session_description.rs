@@ -1,4 +1,4 @@
-use crate::{tokenizers::*, TResult, TokenizerError};
+use crate::{parser_utils::line_endings, tokenizers::*, Diagnostic, TResult, TokenizerError};
 use std::convert::TryInto;
 use vec1::Vec1;
 
@@ -23,10 +23,23 @@ impl<'a> Tokenizer<'a> {
     pub fn tokenize(part: &'a str) -> TResult<'a, Self> {
         use nom::multi::many1;
 
+        //mandatory lines that are missing are reported where they were expected
+        let expect = |rem: &str, prefix: &str, element: &'static str| match rem.starts_with(prefix)
+        {
+            true => Ok(()),
+            false => Err(nom::Err::Error(TokenizerError {
+                remaining: Some(rem.len()),
+                ..TokenizerError::missing_line(element)
+            })),
+        };
+
+        expect(part, "v=", "version")?;
         let (rem, version) = value::Tokenizer::tokenize(part)
             .map_err(|e| TokenizerError::part_with_error("version", part, e).into())?;
+        expect(rem, "o=", "origin")?;
         let (rem, origin) = origin::Tokenizer::tokenize(rem)
             .map_err(|e| TokenizerError::part_with_error("origin", rem, e).into())?;
+        expect(rem, "s=", "session name")?;
         let (rem, session_name) = value::Tokenizer::tokenize(rem)
             .map_err(|e| TokenizerError::part_with_error("session name", rem, e).into())?;
         let (rem, session_info) = match rem.starts_with("i=") {
@@ -68,6 +81,7 @@ impl<'a> Tokenizer<'a> {
                 .map_err(|e| TokenizerError::part_with_error("bandwidth", rem, e).into())?,
             false => (rem, vec![]),
         };
+        expect(rem, "t=", "time")?;
         let (rem, times) = many1(time::Tokenizer::tokenize)(rem)?;
         let (rem, key) = match rem.starts_with("k=") {
             true => {
@@ -101,9 +115,9 @@ impl<'a> Tokenizer<'a> {
                 phones,
                 connection,
                 bandwidths,
-                times: times.try_into().map_err(|_| {
-                    nom::Err::Error(TokenizerError::part_with_input("time", "missing line(s)"))
-                })?,
+                times: times
+                    .try_into()
+                    .map_err(|_| nom::Err::Error(TokenizerError::missing_line("time")))?,
                 key,
                 attributes,
                 media_descriptions,
@@ -112,6 +126,352 @@ impl<'a> Tokenizer<'a> {
     }
 }
 
+impl<'a> Tokenizer<'a> {
+    /// Tokenizes an SDP message accepting the lines in any order. Lines that violate the order
+    /// of RFC8866 are reported as warnings: session level lines after the first media line are
+    /// moved to the session, while duplicates of single occurrence lines are ignored. Repeat
+    /// (`r=`) and zone (`z=`) lines are attached to the preceding time line. Tokenizing stops at
+    /// the first unknown line.
+    pub fn tokenize_lenient(part: &'a str) -> TResult<'a, (Self, Vec<Diagnostic>)> {
+        let mut warnings: Vec<Diagnostic> = vec![];
+        let mut version = None;
+        let mut origin = None;
+        let mut session_name = None;
+        let mut session_info = None;
+        let mut uri = None;
+        let mut emails = vec![];
+        let mut phones = vec![];
+        let mut connection = None;
+        let mut bandwidths = vec![];
+        let mut times: Vec<time::Tokenizer<'a>> = vec![];
+        let mut key = None;
+        let mut attributes = vec![];
+        let mut media_descriptions: Vec<media_description::Tokenizer<'a>> = vec![];
+
+        let mut session_order = 0;
+        let mut media_order = 0;
+        let mut rem = part;
+        //the line number is kept up to date with the newlines of the lines consumed since the
+        //previous iteration, so that each line is scanned once
+        let mut line = 1;
+        let mut counted = part;
+        loop {
+            line += line_endings(&counted[..counted.len() - rem.len()]);
+            counted = rem;
+            let prefix = match rem.get(..2) {
+                Some(prefix) if prefix.ends_with('=') => prefix,
+                _ => break,
+            };
+            let in_media = !media_descriptions.is_empty();
+
+            //media level lines, the rest are always session level lines
+            if let (true, Some(order)) = (in_media, media_line_order(prefix)) {
+                if order < media_order {
+                    warnings.push(out_of_order(line, prefix));
+                }
+                media_order = media_order.max(order);
+
+                let media_description = media_descriptions.last_mut().expect("in media");
+                rem = match prefix {
+                    "m=" => unreachable!(),
+                    "i=" => {
+                        let (rem, info) = value::Tokenizer::tokenize(rem).map_err(|e| {
+                            TokenizerError::part_with_error("media info", rem, e).into()
+                        })?;
+                        match media_description.info {
+                            Some(_) => warnings.push(duplicate(line, prefix)),
+                            None => media_description.info = Some(info),
+                        }
+                        rem
+                    }
+                    "c=" => {
+                        let (rem, connection) =
+                            connection::Tokenizer::tokenize(rem).map_err(|e| {
+                                TokenizerError::part_with_error("media connection", rem, e).into()
+                            })?;
+                        media_description.connections.push(connection);
+                        rem
+                    }
+                    "b=" => {
+                        let (rem, bandwidth) =
+                            key_value::Tokenizer::tokenize(rem).map_err(|e| {
+                                TokenizerError::part_with_error("media bandwidth", rem, e).into()
+                            })?;
+                        media_description.bandwidths.push(bandwidth);
+                        rem
+                    }
+                    "k=" => {
+                        let (rem, key) = key_optvalue::Tokenizer::tokenize(rem).map_err(|e| {
+                            TokenizerError::part_with_error("media key", rem, e).into()
+                        })?;
+                        match media_description.key {
+                            Some(_) => warnings.push(duplicate(line, prefix)),
+                            None => media_description.key = Some(key),
+                        }
+                        rem
+                    }
+                    _ => {
+                        let (rem, attribute) =
+                            key_optvalue::Tokenizer::tokenize(rem).map_err(|e| {
+                                TokenizerError::part_with_error("media attribute", rem, e).into()
+                            })?;
+                        media_description.attributes.push(attribute);
+                        rem
+                    }
+                };
+                continue;
+            }
+
+            let order = match session_line_order(prefix) {
+                Some(order) => order,
+                None if prefix == "m=" => {
+                    let (rem_, media) = media::Tokenizer::tokenize(rem).map_err(|e| {
+                        TokenizerError::part_with_error("media description(s)", rem, e).into()
+                    })?;
+                    media_descriptions.push(media_description::Tokenizer {
+                        media,
+                        info: None,
+                        connections: vec![],
+                        bandwidths: vec![],
+                        key: None,
+                        attributes: vec![],
+                    });
+                    media_order = 0;
+                    rem = rem_;
+                    continue;
+                }
+                None => break,
+            };
+            if in_media {
+                warnings.push(Diagnostic::warning(
+                    line,
+                    format!("session level {} line after media description(s)", prefix),
+                ));
+            } else if order < session_order {
+                warnings.push(out_of_order(line, prefix));
+            }
+            session_order = session_order.max(order);
+
+            rem = match prefix {
+                "v=" => set_once(
+                    &mut version,
+                    value::Tokenizer::tokenize(rem)
+                        .map_err(|e| TokenizerError::part_with_error("version", rem, e).into())?,
+                    (line, prefix),
+                    &mut warnings,
+                ),
+                "o=" => set_once(
+                    &mut origin,
+                    origin::Tokenizer::tokenize(rem)
+                        .map_err(|e| TokenizerError::part_with_error("origin", rem, e).into())?,
+                    (line, prefix),
+                    &mut warnings,
+                ),
+                "s=" => set_once(
+                    &mut session_name,
+                    value::Tokenizer::tokenize(rem).map_err(|e| {
+                        TokenizerError::part_with_error("session name", rem, e).into()
+                    })?,
+                    (line, prefix),
+                    &mut warnings,
+                ),
+                "i=" => set_once(
+                    &mut session_info,
+                    value::Tokenizer::tokenize(rem).map_err(|e| {
+                        TokenizerError::part_with_error("session info", rem, e).into()
+                    })?,
+                    (line, prefix),
+                    &mut warnings,
+                ),
+                "u=" => set_once(
+                    &mut uri,
+                    value::Tokenizer::tokenize(rem)
+                        .map_err(|e| TokenizerError::part_with_error("URI", rem, e).into())?,
+                    (line, prefix),
+                    &mut warnings,
+                ),
+                "e=" => {
+                    let (rem, email) = value::Tokenizer::tokenize(rem)
+                        .map_err(|e| TokenizerError::part_with_error("email", rem, e).into())?;
+                    emails.push(email);
+                    rem
+                }
+                "p=" => {
+                    let (rem, phone) = value::Tokenizer::tokenize(rem)
+                        .map_err(|e| TokenizerError::part_with_error("phone", rem, e).into())?;
+                    phones.push(phone);
+                    rem
+                }
+                "c=" => set_once(
+                    &mut connection,
+                    connection::Tokenizer::tokenize(rem).map_err(|e| {
+                        TokenizerError::part_with_error("connection", rem, e).into()
+                    })?,
+                    (line, prefix),
+                    &mut warnings,
+                ),
+                "b=" => {
+                    let (rem, bandwidth) = key_value::Tokenizer::tokenize(rem)
+                        .map_err(|e| TokenizerError::part_with_error("bandwidth", rem, e).into())?;
+                    bandwidths.push(bandwidth);
+                    rem
+                }
+                "t=" => {
+                    let (rem, time) = time::Tokenizer::tokenize(rem)
+                        .map_err(|e| TokenizerError::part_with_error("time", rem, e).into())?;
+                    times.push(time);
+                    rem
+                }
+                "r=" => {
+                    let (rem_, repeat) = time::repeat::Tokenizer::tokenize(rem)
+                        .map_err(|e| TokenizerError::part_with_error("repeat", rem, e).into())?;
+                    match times.last_mut() {
+                        Some(time) => time.repeat.push(repeat),
+                        None => warnings.push(Diagnostic::warning(
+                            line,
+                            "r= line without a time line, ignoring".into(),
+                        )),
+                    }
+                    rem_
+                }
+                "z=" => {
+                    let (rem_, zone) = time::zone::Tokenizer::tokenize(rem)
+                        .map_err(|e| TokenizerError::part_with_error("zone", rem, e).into())?;
+                    match times.last_mut() {
+                        Some(time) if time.zone.is_none() => time.zone = Some(zone),
+                        Some(_) => warnings.push(duplicate(line, prefix)),
+                        None => warnings.push(Diagnostic::warning(
+                            line,
+                            "z= line without a time line, ignoring".into(),
+                        )),
+                    }
+                    rem_
+                }
+                "k=" => set_once(
+                    &mut key,
+                    key_optvalue::Tokenizer::tokenize(rem)
+                        .map_err(|e| TokenizerError::part_with_error("key", rem, e).into())?,
+                    (line, prefix),
+                    &mut warnings,
+                ),
+                _ => {
+                    let (rem, attribute) = key_optvalue::Tokenizer::tokenize(rem).map_err(|e| {
+                        TokenizerError::part_with_error("attribute(s)", rem, e).into()
+                    })?;
+                    attributes.push(attribute);
+                    rem
+                }
+            };
+        }
+
+        let missing = |line: &'static str| nom::Err::Error(TokenizerError::missing_line(line));
+
+        Ok((
+            rem,
+            (
+                Tokenizer {
+                    version: version.ok_or_else(|| missing("version"))?,
+                    origin: origin.ok_or_else(|| missing("origin"))?,
+                    session_name: session_name.ok_or_else(|| missing("session name"))?,
+                    session_info,
+                    uri,
+                    emails,
+                    phones,
+                    connection,
+                    bandwidths,
+                    times: times
+                        .try_into()
+                        .map_err(|_| nom::Err::Error(TokenizerError::missing_line("time")))?,
+                    key,
+                    attributes,
+                    media_descriptions,
+                },
+                warnings,
+            ),
+        ))
+    }
+}
+
+/// Tokenizes a single line of an SDP message with the tokenizer of its type, failing unless the
+/// whole line is consumed. Used to find the broken lines of an SDP message, so that they can be
+/// skipped.
+pub(crate) fn tokenize_line(line: &str) -> Result<(), nom::Err<TokenizerError>> {
+    let tokenized = match line.get(..2) {
+        Some("v=") => value::Tokenizer::<'v'>::tokenize(line).map(|(rem, _)| rem),
+        Some("o=") => origin::Tokenizer::tokenize(line).map(|(rem, _)| rem),
+        Some("s=") => value::Tokenizer::<'s'>::tokenize(line).map(|(rem, _)| rem),
+        Some("i=") => value::Tokenizer::<'i'>::tokenize(line).map(|(rem, _)| rem),
+        Some("u=") => value::Tokenizer::<'u'>::tokenize(line).map(|(rem, _)| rem),
+        Some("e=") => value::Tokenizer::<'e'>::tokenize(line).map(|(rem, _)| rem),
+        Some("p=") => value::Tokenizer::<'p'>::tokenize(line).map(|(rem, _)| rem),
+        Some("c=") => connection::Tokenizer::tokenize(line).map(|(rem, _)| rem),
+        Some("b=") => key_value::Tokenizer::<'b'>::tokenize(line).map(|(rem, _)| rem),
+        Some("t=") => time::active::Tokenizer::tokenize(line).map(|(rem, _)| rem),
+        Some("r=") => time::repeat::Tokenizer::tokenize(line).map(|(rem, _)| rem),
+        Some("z=") => time::zone::Tokenizer::tokenize(line).map(|(rem, _)| rem),
+        Some("k=") => key_optvalue::Tokenizer::<'k'>::tokenize(line).map(|(rem, _)| rem),
+        Some("a=") => key_optvalue::Tokenizer::<'a'>::tokenize(line).map(|(rem, _)| rem),
+        Some("m=") => media::Tokenizer::tokenize(line).map(|(rem, _)| rem),
+        _ => {
+            return Err(nom::Err::Error(TokenizerError::part_with_input(
+                "line", line,
+            )))
+        }
+    };
+
+    match tokenized {
+        Ok(rem) if rem.trim().is_empty() => Ok(()),
+        Ok(rem) => Err(nom::Err::Error(TokenizerError::part_with_input(
+            "line", rem,
+        ))),
+        Err(e) => Err(TokenizerError::part_with_error("line", line, e).into()),
+    }
+}
+
+//the position of the line in the session level order of RFC8866, repeat and zone lines are
+//part of the time
+fn session_line_order(prefix: &str) -> Option<usize> {
+    [
+        "v=", "o=", "s=", "i=", "u=", "e=", "p=", "c=", "b=", "t=", "k=", "a=",
+    ]
+    .iter()
+    .position(|p| *p == prefix)
+    .or(match prefix {
+        "r=" | "z=" => Some(9),
+        _ => None,
+    })
+}
+
+//the position of the line in the media level order of RFC8866
+fn media_line_order(prefix: &str) -> Option<usize> {
+    ["m=", "i=", "c=", "b=", "k=", "a="]
+        .iter()
+        .position(|p| *p == prefix)
+        .filter(|order| *order > 0)
+}
+
+fn set_once<'a, T>(
+    field: &mut Option<T>,
+    (rem, value): (&'a str, T),
+    (line, prefix): (usize, &str),
+    warnings: &mut Vec<Diagnostic>,
+) -> &'a str {
+    match field {
+        Some(_) => warnings.push(duplicate(line, prefix)),
+        None => *field = Some(value),
+    }
+
+    rem
+}
+
+fn out_of_order(line: usize, prefix: &str) -> Diagnostic {
+    Diagnostic::warning(line, format!("{} line out of order", prefix))
+}
+
+fn duplicate(line: usize, prefix: &str) -> Diagnostic {
+    Diagnostic::warning(line, format!("duplicate {} line, ignoring", prefix))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -313,4 +673,26 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn tokenize_lenient1() {
+        let part = concat!(
+            "v=0\r",
+            "o=Alice 2890844526 2890842807 IN IP4 10.47.16.5\r\n",
+            "s=-\n",
+            "s=other\r",
+            "t=2854678930 2854679000\r",
+        );
+
+        let (rem, (_, warnings)) = Tokenizer::tokenize_lenient(part).unwrap();
+
+        assert_eq!(rem, "");
+        assert_eq!(
+            warnings
+                .into_iter()
+                .map(|warning| warning.line)
+                .collect::<Vec<_>>(),
+            vec![Some(4)]
+        );
+    }
 }
@@ -30,7 +30,7 @@ mod tests {
 
     #[test]
     fn tokenizer() {
-        let time = concat!("t=3724394400 3724398000\r\nsomething");
+        let time = "t=3724394400 3724398000\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(time),
@@ -49,7 +49,7 @@ mod tests {
 
     #[test]
     fn tokenizer1() {
-        let repeat = concat!("r=604800 3600 0\r\nsomething");
+        let repeat = "r=604800 3600 0\r\nsomething";
 
         assert_eq!(
             Ok(("something", ("604800", "3600", vec!["0"]).into())),
@@ -59,7 +59,7 @@ mod tests {
 
     #[test]
     fn tokenizer2() {
-        let repeat = concat!("r=604800 3600 0 90000\r\nsomething");
+        let repeat = "r=604800 3600 0 90000\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(repeat),
@@ -69,7 +69,7 @@ mod tests {
 
     #[test]
     fn tokenizer3() {
-        let repeat = concat!("r=7d 1h 0 25h\r\nsomething");
+        let repeat = "r=7d 1h 0 25h\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(repeat),
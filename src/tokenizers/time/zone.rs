@@ -40,7 +40,7 @@ mod tests {
 
     #[test]
     fn tokenizer1() {
-        let zone = concat!("z=3730928400 -1h\r\nsomething");
+        let zone = "z=3730928400 -1h\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(zone),
@@ -50,7 +50,7 @@ mod tests {
 
     #[test]
     fn tokenizer2() {
-        let zone = concat!("z=3730928400 -1h 3749680800 0\r\nsomething");
+        let zone = "z=3730928400 -1h 3749680800 0\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(zone),
@@ -63,7 +63,7 @@ mod tests {
 
     #[test]
     fn tokenizer3() {
-        let zone = concat!("z=3730928400 -1h 3749680800 0h\r\nsomething");
+        let zone = "z=3730928400 -1h 3749680800 0h\r\nsomething";
 
         assert_eq!(
             Tokenizer::tokenize(zone),
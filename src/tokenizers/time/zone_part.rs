@@ -31,7 +31,7 @@ mod tests {
 
     #[test]
     fn tokenizer_part1() {
-        let zone_part = concat!("3730928400 -1h");
+        let zone_part = "3730928400 -1h";
 
         assert_eq!(
             Tokenizer::tokenize(zone_part),
@@ -41,7 +41,7 @@ mod tests {
 
     #[test]
     fn tokenizer_part2() {
-        let zone_part = concat!("3730928400 0h");
+        let zone_part = "3730928400 0h";
 
         assert_eq!(
             Tokenizer::tokenize(zone_part),
@@ -34,11 +34,25 @@ mod tests {
 
     #[test]
     fn tokenizer() {
-        let value = concat!("i=a value here #sdp #rocks\r\nsomething",);
+        let value = "i=a value here #sdp #rocks\r\nsomething";
 
         assert_eq!(
             Tokenizer::<'i'>::tokenize(value),
             Ok(("something", "a value here #sdp #rocks".into())),
         );
     }
+
+    #[test]
+    fn tokenizer2() {
+        let value = "i=a value here\rs=something\r\n";
+
+        assert_eq!(
+            Tokenizer::<'i'>::tokenize(value),
+            Ok(("s=something\r\n", "a value here".into())),
+        );
+        assert_eq!(
+            Tokenizer::<'i'>::tokenize("i=a value here\n\r\n"),
+            Ok(("\r\n", "a value here".into())),
+        );
+    }
 }